@@ -0,0 +1,38 @@
+use std::fs;
+use std::hash::{DefaultHasher, Hasher};
+use std::path::Path;
+
+// Rebuild when a migration changes so `sqlx::migrate!` embeds the latest files.
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+    println!("cargo:rerun-if-changed=src");
+
+    // Fingerprint of everything compiled into the binary, so every instance of one
+    // build agrees on its ETags while a build that renders differently does not.
+    let mut hasher = DefaultHasher::new();
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    hash_dir(Path::new("src"), &mut hasher);
+    hash_dir(Path::new("migrations"), &mut hasher);
+    println!("cargo:rustc-env=BUILD_FINGERPRINT={:016x}", hasher.finish());
+}
+
+/// Hashes the paths and contents of every file under `dir`, in a stable order.
+fn hash_dir(dir: &Path, hasher: &mut DefaultHasher) {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("Failed to read a directory entry").path())
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            hash_dir(&path, hasher);
+        } else {
+            hasher.write(path.to_string_lossy().as_bytes());
+            hasher.write(
+                &fs::read(&path)
+                    .unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e)),
+            );
+        }
+    }
+}
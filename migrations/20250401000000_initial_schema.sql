@@ -0,0 +1,49 @@
+-- Core portfolio schema. Written to be safe against databases that were created by
+-- hand before migrations existed: every object is only created when missing.
+
+DO $$
+BEGIN
+    CREATE TYPE proficiency AS ENUM ('Beginner', 'Intermediate', 'Advanced', 'Expert');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END
+$$;
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id SERIAL PRIMARY KEY,
+    start_date DATE NOT NULL,
+    end_date DATE,
+    is_current_job BOOLEAN NOT NULL DEFAULT FALSE,
+    company_name TEXT NOT NULL,
+    company_website TEXT NOT NULL DEFAULT '',
+    description TEXT NOT NULL DEFAULT '',
+    roles TEXT NOT NULL DEFAULT '',
+    responsibilities TEXT NOT NULL DEFAULT ''
+);
+
+CREATE TABLE IF NOT EXISTS skills (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    official_site_url TEXT NOT NULL DEFAULT '',
+    proficiency proficiency NOT NULL DEFAULT 'Beginner',
+    parent_id INTEGER REFERENCES skills (id)
+);
+
+CREATE TABLE IF NOT EXISTS projects (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    github_url TEXT,
+    job_id INTEGER REFERENCES jobs (id)
+);
+
+CREATE TABLE IF NOT EXISTS projects_skills (
+    project_id INTEGER NOT NULL REFERENCES projects (id) ON DELETE CASCADE,
+    skill_id INTEGER NOT NULL REFERENCES skills (id),
+    PRIMARY KEY (project_id, skill_id)
+);
+
+CREATE INDEX IF NOT EXISTS projects_job_id_idx ON projects (job_id);
+CREATE INDEX IF NOT EXISTS projects_skills_skill_id_idx ON projects_skills (skill_id);
+CREATE INDEX IF NOT EXISTS skills_parent_id_idx ON skills (parent_id);
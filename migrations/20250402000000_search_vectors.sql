@@ -0,0 +1,26 @@
+-- Full-text search over projects, jobs and skills. Each table gets a stored, weighted
+-- tsvector (titles rank above body text) kept current by Postgres, plus a GIN index.
+
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', name), 'A') ||
+        setweight(to_tsvector('english', description), 'B')
+    ) STORED;
+
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', company_name), 'A') ||
+        setweight(to_tsvector('english', roles), 'B') ||
+        setweight(to_tsvector('english', description), 'C') ||
+        setweight(to_tsvector('english', responsibilities), 'C')
+    ) STORED;
+
+ALTER TABLE skills ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', name), 'A') ||
+        setweight(to_tsvector('english', description), 'B')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS projects_search_vector_idx ON projects USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS jobs_search_vector_idx ON jobs USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS skills_search_vector_idx ON skills USING GIN (search_vector);
@@ -0,0 +1,45 @@
+-- Creation and modification times of projects and jobs, for the feeds. Rows that
+-- predate this migration get the time it ran.
+
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT now();
+
+ALTER TABLE jobs
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT now();
+
+-- Bumps updated_at on every update
+CREATE OR REPLACE FUNCTION set_updated_at() RETURNS trigger AS $$
+BEGIN
+    NEW.updated_at = now();
+    RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS projects_set_updated_at ON projects;
+CREATE TRIGGER projects_set_updated_at
+    BEFORE UPDATE ON projects
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+DROP TRIGGER IF EXISTS jobs_set_updated_at ON jobs;
+CREATE TRIGGER jobs_set_updated_at
+    BEFORE UPDATE ON jobs
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+-- Linking or unlinking a skill changes the project too
+CREATE OR REPLACE FUNCTION touch_project() RETURNS trigger AS $$
+BEGIN
+    UPDATE projects SET updated_at = now()
+    WHERE id = COALESCE(NEW.project_id, OLD.project_id);
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS projects_skills_touch_project ON projects_skills;
+CREATE TRIGGER projects_skills_touch_project
+    AFTER INSERT OR DELETE ON projects_skills
+    FOR EACH ROW
+    EXECUTE FUNCTION touch_project();
@@ -0,0 +1,45 @@
+-- Change counters for the portfolio tables, used as HTTP validators (ETag and
+-- Last-Modified). Every statement that writes to a table bumps its row.
+
+CREATE TABLE IF NOT EXISTS table_versions (
+    table_name TEXT PRIMARY KEY,
+    version BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+INSERT INTO table_versions (table_name)
+VALUES ('jobs'), ('projects'), ('skills'), ('projects_skills')
+ON CONFLICT (table_name) DO NOTHING;
+
+CREATE OR REPLACE FUNCTION bump_table_version() RETURNS trigger AS $$
+BEGIN
+    UPDATE table_versions
+    SET version = version + 1, updated_at = now()
+    WHERE table_name = TG_TABLE_NAME;
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS jobs_bump_version ON jobs;
+CREATE TRIGGER jobs_bump_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON jobs
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION bump_table_version();
+
+DROP TRIGGER IF EXISTS projects_bump_version ON projects;
+CREATE TRIGGER projects_bump_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON projects
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION bump_table_version();
+
+DROP TRIGGER IF EXISTS skills_bump_version ON skills;
+CREATE TRIGGER skills_bump_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON skills
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION bump_table_version();
+
+DROP TRIGGER IF EXISTS projects_skills_bump_version ON projects_skills;
+CREATE TRIGGER projects_skills_bump_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON projects_skills
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION bump_table_version();
@@ -0,0 +1,13 @@
+-- Announce every change to the portfolio tables on the `table_changed` channel, with
+-- the table name as payload, so the API can drop cached responses as soon as the
+-- writing transaction commits.
+
+CREATE OR REPLACE FUNCTION bump_table_version() RETURNS trigger AS $$
+BEGIN
+    UPDATE table_versions
+    SET version = version + 1, updated_at = now()
+    WHERE table_name = TG_TABLE_NAME;
+    PERFORM pg_notify('table_changed', TG_TABLE_NAME);
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
@@ -1,4 +1,5 @@
-use utoipa::OpenApi;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -7,28 +8,117 @@ use utoipa::OpenApi;
         crate::handlers::projects::get_project_by_id,
         crate::handlers::projects::get_projects_by_job,
         crate::handlers::projects::get_projects_by_skill,
+        crate::handlers::projects::create_project,
+        crate::handlers::projects::update_project,
+        crate::handlers::projects::patch_project,
+        crate::handlers::projects::delete_project,
+        crate::handlers::projects::replace_project_skills,
+        crate::handlers::projects::add_project_skill,
+        crate::handlers::projects::remove_project_skill,
         crate::handlers::jobs::get_jobs,
         crate::handlers::jobs::get_job_by_id,
+        crate::handlers::jobs::create_job,
+        crate::handlers::jobs::update_job,
+        crate::handlers::jobs::patch_job,
+        crate::handlers::jobs::delete_job,
         crate::handlers::skills::get_skills,
         crate::handlers::skills::get_skill_by_id,
+        crate::handlers::skills::get_skill_tree,
+        crate::handlers::skills::get_skill_ancestors,
+        crate::handlers::skills::get_skill_descendants,
+        crate::handlers::skills::create_skill,
+        crate::handlers::skills::update_skill,
+        crate::handlers::skills::patch_skill,
+        crate::handlers::skills::delete_skill,
+        crate::handlers::search::search,
+        crate::handlers::export::get_json_resume,
+        crate::handlers::export::get_resume_pdf,
+        crate::handlers::export::get_resume_markdown,
+        crate::handlers::export::get_resume_text,
+        crate::handlers::render::render_page,
+        crate::handlers::feeds::get_projects_feed,
+        crate::handlers::feeds::get_activity_feed,
+        crate::handlers::seo::get_person_jsonld,
+        crate::handlers::seo::get_project_jsonld,
+        crate::handlers::seo::get_sitemap,
+        crate::handlers::graphql::graphql,
+        crate::handlers::graphql::graphiql,
+        crate::handlers::cache::get_cache_stats,
+        crate::handlers::metrics::get_metrics,
+        crate::handlers::health::get_healthz,
+        crate::handlers::health::get_readyz,
     ),
     components(
         schemas(
             crate::models::project::Project,
+            crate::models::project::NewProject,
+            crate::models::project::ProjectPatch,
+            crate::models::project::ProjectSkillIds,
+            crate::models::project::MatchedProject,
             crate::models::skill::Skill,
+            crate::models::skill::NewSkill,
+            crate::models::skill::SkillPatch,
+            crate::models::skill::SkillNode,
+            crate::models::skill::RelatedSkill,
             crate::models::job::Job,
-            crate::db::proficiency_enum::Proficiency
+            crate::models::job::NewJob,
+            crate::models::job::JobPatch,
+            crate::db::proficiency_enum::Proficiency,
+            crate::models::search::SearchHit,
+            crate::models::search::SearchKind,
+            crate::models::profile::Profile,
+            crate::export::json_resume::JsonResume,
+            crate::export::pdf::PageSize,
+            crate::seo::PersonLd,
+            crate::seo::CreativeWorkLd,
+            crate::response_cache::CacheStats,
+            crate::health::HealthReport,
+            crate::health::Check,
+            crate::health::CheckStatus,
+            crate::error::ProblemDetails
         )
     ),
+    modifiers(&SecurityAddon),
     tags(
         (name = "projects", description = "Project management endpoints"),
         (name = "jobs", description = "Job history endpoints"),
-        (name = "skills", description = "Skills management endpoints")
+        (name = "skills", description = "Skills management endpoints"),
+        (name = "search", description = "Full-text search across the portfolio"),
+        (name = "export", description = "Résumé exports built from the portfolio"),
+        (name = "render", description = "HTML portfolio pages rendered with themes"),
+        (name = "feeds", description = "Atom and RSS feeds of portfolio changes"),
+        (name = "seo", description = "schema.org JSON-LD and sitemap for search engines"),
+        (name = "graphql", description = "GraphQL API over jobs, projects and skills"),
+        (name = "cache", description = "In-memory response cache"),
+        (name = "metrics", description = "Prometheus metrics"),
+        (name = "health", description = "Liveness and readiness probes")
     ),
     info(
         title = "Portfolio API",
         version = "0.1.0",
-        description = "API for managing portfolio projects, skills, and job history"
+        description = "API for managing portfolio projects, skills, and job history. \
+            Every error is returned as an RFC 7807 `application/problem+json` document \
+            (see the `ProblemDetails` schema) whose `type` is stable across releases."
     )
 )]
 pub struct ApiDoc;
+
+/// Registers the bearer token scheme used by the mutating routes, so Swagger UI can send it
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some(
+                        "API token with the `<resource>:write` scope of the route being called",
+                    ))
+                    .build(),
+            ),
+        );
+    }
+}
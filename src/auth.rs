@@ -0,0 +1,76 @@
+use crate::db::api_tokens_db;
+use crate::error::ApiError;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use sqlx::PgPool;
+
+/// Scope a token needs to call the mutating routes of a resource
+pub const JOBS_WRITE: &str = "jobs:write";
+pub const PROJECTS_WRITE: &str = "projects:write";
+pub const SKILLS_WRITE: &str = "skills:write";
+
+/// State for [`require_write_scope`]: where to look tokens up and which scope to demand
+#[derive(Clone)]
+pub struct RequiredScope {
+    pub pool: PgPool,
+    pub scope: &'static str,
+}
+
+impl RequiredScope {
+    pub fn new(pool: PgPool, scope: &'static str) -> Self {
+        Self { pool, scope }
+    }
+}
+
+/// Extracts the token from `Authorization: Bearer <token>` or, failing that, `X-API-Key`.
+fn extract_token(headers: &HeaderMap) -> Option<&str> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    bearer
+        .or_else(|| {
+            headers
+                .get("x-api-key")
+                .and_then(|value| value.to_str().ok())
+        })
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Middleware that lets safe methods through and requires a valid API token carrying
+/// the configured scope for everything else.
+///
+/// Responds with 401 when the token is missing, unknown or revoked, and 403 when the
+/// token is valid but lacks the scope.
+pub async fn require_write_scope(
+    State(required): State<RequiredScope>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) {
+        return Ok(next.run(request).await);
+    }
+
+    let token = extract_token(request.headers())
+        .ok_or_else(|| ApiError::Unauthorized("Missing API token".to_string()))?;
+
+    let scopes = api_tokens_db::authenticate_token(&required.pool, token)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("Invalid or revoked API token".to_string()))?;
+
+    if !scopes.iter().any(|scope| scope == required.scope) {
+        return Err(ApiError::Forbidden(format!(
+            "API token lacks the {} scope",
+            required.scope
+        )));
+    }
+
+    Ok(next.run(request).await)
+}
@@ -0,0 +1,123 @@
+use crate::degraded::DegradedMode;
+use crate::health::Health;
+use crate::models::profile::Profile;
+use crate::render::Themes;
+use crate::response_cache::ResponseCache;
+use crate::seo::SiteConfig;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::info;
+
+/// Loads the résumé profile from the JSON file named by `PROFILE_PATH`.
+///
+/// Without `PROFILE_PATH` the profile is empty, so exports only carry the data
+/// stored in the database.
+pub fn load_profile() -> Result<Profile, String> {
+    let Ok(path) = std::env::var("PROFILE_PATH") else {
+        info!("PROFILE_PATH is not set; exports will have an empty profile");
+        return Ok(Profile::default());
+    };
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read profile {}: {}", path, e))?;
+    let profile = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse profile {}: {}", path, e))?;
+
+    info!("Loaded profile from {}", path);
+    Ok(profile)
+}
+
+/// Loads the built-in HTML themes plus any theme directories found in `THEMES_DIR`
+pub fn load_themes() -> Result<Themes, String> {
+    let mut themes = Themes::builtin();
+    if let Ok(dir) = std::env::var("THEMES_DIR") {
+        themes.load_dir(Path::new(&dir))?;
+    }
+
+    info!(
+        "Available themes: {}",
+        themes.names().collect::<Vec<_>>().join(", ")
+    );
+    Ok(themes)
+}
+
+/// Public site the API backs, used when `SITE_BASE_URL` is not set
+pub const DEFAULT_SITE_BASE_URL: &str = "https://sindbadmcintosh.com";
+
+/// Loads the public site's base URL from `SITE_BASE_URL`, for the JSON-LD and sitemap
+pub fn load_site() -> Result<SiteConfig, String> {
+    let base_url =
+        std::env::var("SITE_BASE_URL").unwrap_or_else(|_| DEFAULT_SITE_BASE_URL.to_string());
+    let site = SiteConfig::new(&base_url).map_err(|e| format!("Invalid SITE_BASE_URL: {}", e))?;
+
+    info!("Site pages are under {}", site.base_url());
+    Ok(site)
+}
+
+/// Reads a number of seconds from the environment variable `name`
+fn duration_from_env(name: &str, default: Duration) -> Result<Duration, String> {
+    match std::env::var(name) {
+        Ok(seconds) => seconds
+            .parse()
+            .map(Duration::from_secs)
+            .map_err(|e| format!("Invalid {}: {}", name, e)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// How long cached responses live while table change notifications are not arriving,
+/// used when `CACHE_TTL_SECONDS` is not set
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Creates the response cache, with the fallback TTL from `CACHE_TTL_SECONDS`
+pub fn load_cache() -> Result<ResponseCache, String> {
+    let ttl = duration_from_env("CACHE_TTL_SECONDS", DEFAULT_CACHE_TTL)?;
+
+    info!("Cached responses fall back to a {:?} TTL", ttl);
+    Ok(ResponseCache::new(ttl))
+}
+
+/// How often a snapshot is taken, used when `SNAPSHOT_INTERVAL_SECONDS` is not set
+pub const DEFAULT_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Sets up degraded mode with the snapshot file named by `SNAPSHOT_PATH`, loading the
+/// snapshot if the file exists.
+///
+/// Without `SNAPSHOT_PATH` no snapshots are kept, so the server cannot start without
+/// the database.
+pub fn load_degraded_mode() -> Result<DegradedMode, String> {
+    let Ok(path) = std::env::var("SNAPSHOT_PATH") else {
+        info!("SNAPSHOT_PATH is not set; no snapshot will be served while the database is down");
+        return Ok(DegradedMode::new(None));
+    };
+
+    let degraded = DegradedMode::new(Some(PathBuf::from(&path)));
+    match degraded.load_snapshot()? {
+        Some(taken_at) => info!("Loaded snapshot from {} taken at {}", path, taken_at),
+        None => info!("No snapshot at {} yet", path),
+    }
+    Ok(degraded)
+}
+
+/// How often to take a snapshot, from `SNAPSHOT_INTERVAL_SECONDS`
+pub fn load_snapshot_interval() -> Result<Duration, String> {
+    duration_from_env("SNAPSHOT_INTERVAL_SECONDS", DEFAULT_SNAPSHOT_INTERVAL)
+}
+
+/// Default deadline of each readiness check
+pub const DEFAULT_READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Sets up the health probes, with the readiness deadline from `READINESS_TIMEOUT_SECONDS`
+pub fn load_health() -> Result<Health, String> {
+    let timeout = duration_from_env("READINESS_TIMEOUT_SECONDS", DEFAULT_READINESS_TIMEOUT)?;
+    Ok(Health::new(timeout))
+}
+
+/// Default time between failing readiness and shutting down
+pub const DEFAULT_SHUTDOWN_DRAIN: Duration = Duration::from_secs(5);
+
+/// How long to keep serving with readiness failing after a shutdown signal, from
+/// `SHUTDOWN_DRAIN_SECONDS`. It should exceed the orchestrator's readiness probe period.
+pub fn load_shutdown_drain() -> Result<Duration, String> {
+    duration_from_env("SHUTDOWN_DRAIN_SECONDS", DEFAULT_SHUTDOWN_DRAIN)
+}
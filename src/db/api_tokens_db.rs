@@ -0,0 +1,84 @@
+use crate::metrics::QueryTimer;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::{Error, PgPool};
+
+/// Hashes a plaintext API token; only this hash is ever stored or compared.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Generates a new random plaintext API token.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("pat_{}", hex::encode(bytes))
+}
+
+/// Stores the hash of `token` with the given scopes.
+///
+/// # Arguments
+///
+/// * `pool` - The database connection pool
+/// * `name` - A human readable label for the token
+/// * `token` - The plaintext token; it is hashed before being stored
+/// * `scopes` - Scopes granted to the token, e.g. `jobs:write`
+///
+/// # Returns
+///
+/// * `Result<i32, sqlx::Error>` - The ID of the stored token, or a database error
+pub async fn insert_api_token(
+    pool: &PgPool,
+    name: &str,
+    token: &str,
+    scopes: &[String],
+) -> Result<i32, Error> {
+    let _timer = QueryTimer::start("api_tokens_db::insert_api_token");
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO api_tokens (name, token_hash, scopes)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+    )
+    .bind(name)
+    .bind(hash_token(token))
+    .bind(scopes)
+    .fetch_one(pool)
+    .await
+}
+
+/// Generates and stores a new API token, returning the plaintext token.
+///
+/// The plaintext is not recoverable afterwards, so it must be shown to the caller once.
+pub async fn create_api_token(
+    pool: &PgPool,
+    name: &str,
+    scopes: &[String],
+) -> Result<String, Error> {
+    let _timer = QueryTimer::start("api_tokens_db::create_api_token");
+    let token = generate_token();
+    insert_api_token(pool, name, &token, scopes).await?;
+    Ok(token)
+}
+
+/// Looks up a non-revoked token by its plaintext value and records its use.
+///
+/// # Returns
+///
+/// * `Result<Option<Vec<String>>, sqlx::Error>` - The token's scopes, None if the token is
+///   unknown or revoked, or a database error
+pub async fn authenticate_token(pool: &PgPool, token: &str) -> Result<Option<Vec<String>>, Error> {
+    let _timer = QueryTimer::start("api_tokens_db::authenticate_token");
+    sqlx::query_scalar(
+        r#"
+        UPDATE api_tokens
+        SET last_used_at = now()
+        WHERE token_hash = $1 AND revoked_at IS NULL
+        RETURNING scopes
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(pool)
+    .await
+}
@@ -1,12 +1,54 @@
+use sqlx::migrate::{MigrateError, Migrator};
 use sqlx::{Error, PgPool, postgres::PgPoolOptions};
+use std::time::Duration;
 use tracing::info;
 
+/// Schema migrations from `migrations/`, embedded in the binary at compile time
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Applies any pending embedded migrations.
+pub async fn run_migrations(pool: &PgPool) -> Result<(), MigrateError> {
+    info!("Applying database migrations...");
+    MIGRATOR.run(pool).await?;
+    info!("Database schema is up to date");
+    Ok(())
+}
+
+/// Version of the newest embedded migration, which a fully migrated schema has applied
+pub fn expected_migration_version() -> Option<i64> {
+    MIGRATOR.iter().map(|migration| migration.version).max()
+}
+
+/// Version of the newest migration successfully applied to the database, `None` before
+/// the first one
+pub async fn applied_migration_version(pool: &PgPool) -> Result<Option<i64>, Error> {
+    sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+        .fetch_one(pool)
+        .await
+}
+
+/// Whether `RUN_MIGRATIONS` asks for migrations to be applied on startup
+pub fn migrations_enabled() -> bool {
+    std::env::var("RUN_MIGRATIONS")
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// How long a request waits for a pool connection before failing with `PoolTimedOut`,
+/// which is answered with 503 and switches to serving the snapshot. Also bounds the
+/// initial connection attempt.
+pub const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(5);
+
 // Refactored function to accept explicit configuration parameters
-pub async fn connect_with_config(database_url: &str, max_connections: u32) -> Result<PgPool, Error> {
+pub async fn connect_with_config(
+    database_url: &str,
+    max_connections: u32,
+) -> Result<PgPool, Error> {
     info!("Attempting to connect to database...");
 
     let pool = PgPoolOptions::new()
         .max_connections(max_connections)
+        .acquire_timeout(ACQUIRE_TIMEOUT)
         .connect(database_url)
         .await?;
 
@@ -15,8 +57,8 @@ pub async fn connect_with_config(database_url: &str, max_connections: u32) -> Re
     Ok(pool)
 }
 
-// Wrapper function to preserve the original behavior (optional)
-pub async fn connect() -> Result<PgPool, Error> {
+// Reads DATABASE_URL and DATABASE_MAX_CONNECTIONS
+fn config_from_env() -> Result<(String, u32), Error> {
     let database_url = std::env::var("DATABASE_URL")
         .map_err(|_| Error::Configuration("DATABASE_URL environment variable is not set".into()))?;
 
@@ -25,5 +67,29 @@ pub async fn connect() -> Result<PgPool, Error> {
         .parse()
         .unwrap_or(5);
 
-    connect_with_config(&database_url, max_connections).await
-}
\ No newline at end of file
+    Ok((database_url, max_connections))
+}
+
+/// Creates a pool from DATABASE_URL and DATABASE_MAX_CONNECTIONS without connecting,
+/// for starting while the database is down. Connections are opened on first use.
+pub fn connect_lazy() -> Result<PgPool, Error> {
+    let (database_url, max_connections) = config_from_env()?;
+
+    PgPoolOptions::new()
+        .max_connections(max_connections)
+        .acquire_timeout(ACQUIRE_TIMEOUT)
+        .connect_lazy(&database_url)
+}
+
+// Wrapper function reading DATABASE_URL, DATABASE_MAX_CONNECTIONS and RUN_MIGRATIONS
+pub async fn connect() -> Result<PgPool, Error> {
+    let (database_url, max_connections) = config_from_env()?;
+
+    let pool = connect_with_config(&database_url, max_connections).await?;
+
+    if migrations_enabled() {
+        run_migrations(&pool).await?;
+    }
+
+    Ok(pool)
+}
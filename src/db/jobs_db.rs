@@ -1,7 +1,11 @@
-use crate::models::job::Job;
-use sqlx::PgPool;
-use sqlx::Row;
-use sqlx::postgres::PgRow;
+use crate::db::lenient::{DecodeRow, LenientDecode, LenientDecoder};
+use crate::db::pagination::{KeyType, Listing, PageRequest, SortKey};
+use crate::metrics::QueryTimer;
+use crate::models::feed::JobActivity;
+use crate::models::job::{Job, JobFilters, JobPatch, NewJob};
+use crate::models::page::Page;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgExecutor, PgPool};
 
 const JOB_QUERY: &str = r#"
     SELECT 
@@ -17,36 +21,119 @@ const JOB_QUERY: &str = r#"
     FROM jobs
 "#;
 
-fn map_row_to_job(row: PgRow) -> Job {
-    Job {
-        id: row.try_get("id").unwrap_or_default(),
-        start_date: row.try_get("start_date").unwrap_or_default(),
-        end_date: row.try_get("end_date").unwrap_or_default(),
-        is_current_job: row.try_get("is_current_job").unwrap_or_default(),
-        company_name: row.try_get("company_name").unwrap_or_default(),
-        company_website: row.try_get("company_website").unwrap_or_default(),
-        description: row.try_get("description").unwrap_or_default(),
-        roles: row.try_get("roles").unwrap_or_default(),
-        responsibilities: row.try_get("responsibilities").unwrap_or_default(),
+/// Columns `/jobs` can be sorted by
+pub const JOB_SORT_KEYS: &[SortKey] = &[
+    SortKey {
+        name: "start_date",
+        key_type: KeyType::Date,
+    },
+    SortKey {
+        name: "company_name",
+        key_type: KeyType::Text,
+    },
+    SortKey {
+        name: "id",
+        key_type: KeyType::Int,
+    },
+];
+
+/// Most recent job first
+pub const JOB_DEFAULT_SORT: &str = "-start_date";
+
+impl LenientDecode for Job {
+    fn decode_columns(decoder: &mut LenientDecoder<'_>) {
+        decoder.column::<i32>("id");
+        decoder.column::<NaiveDate>("start_date");
+        decoder.column::<Option<NaiveDate>>("end_date");
+        decoder.column::<bool>("is_current_job");
+        decoder.column::<String>("company_name");
+        decoder.column::<String>("company_website");
+        decoder.column::<String>("description");
+        decoder.column::<String>("roles");
+        decoder.column::<String>("responsibilities");
     }
 }
 
-/// Fetches all jobs from the database, ordered by id.
+/// Fetches one page of the jobs matching `filters` from the database.
+///
+/// Rows decode into `T`: `Job` fails on the first undecodable column, while
+/// `Lenient<Job>` keeps the row and reports the column in its `warnings`.
 ///
 /// # Arguments
 ///
 /// * `pool` - The database connection pool
+/// * `filters` - The filters the jobs must match
+/// * `page` - The page size, sort and cursor to fetch
 ///
 /// # Returns
 ///
-/// * `Result<Vec<Job>, sqlx::Error>` - A vector of jobs if successful, or a database error
-pub async fn fetch_jobs(pool: &PgPool) -> Result<Vec<Job>, sqlx::Error> {
-    let rows = sqlx::query(format!("{} ORDER BY start_date DESC", JOB_QUERY).as_str())
-        .map(map_row_to_job)
-        .fetch_all(pool)
-        .await?;
+/// * `Result<Page<T>, sqlx::Error>` - A page of jobs if successful, or a database error
+pub async fn fetch_jobs<T: DecodeRow>(
+    pool: &PgPool,
+    filters: &JobFilters,
+    page: &PageRequest,
+) -> Result<Page<T>, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::fetch_jobs");
+    let mut listing = Listing::new(JOB_QUERY, page);
+    if let Some(current) = filters.current {
+        listing
+            .filter()
+            .push("page_rows.is_current_job = ")
+            .push_bind(current);
+    }
+    if let Some(from) = filters.from {
+        listing
+            .filter()
+            .push("(page_rows.end_date IS NULL OR page_rows.end_date >= ")
+            .push_bind(from)
+            .push(")");
+    }
+    listing.fetch(pool).await
+}
+
+/// Fetches every job, in the default `/jobs` order (most recent first), for exports.
+///
+/// # Arguments
+///
+/// * `pool` - The database connection pool
+///
+/// # Returns
+///
+/// * `Result<Vec<Job>, sqlx::Error>` - All jobs if successful, or a database error
+pub async fn fetch_all_jobs(pool: &PgPool) -> Result<Vec<Job>, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::fetch_all_jobs");
+    let query = format!("{} ORDER BY start_date DESC, id ASC", JOB_QUERY);
+    sqlx::query_as(&query).fetch_all(pool).await
+}
 
-    Ok(rows)
+/// Fetches the jobs with the given IDs, for batched lookups
+pub async fn fetch_jobs_by_ids(pool: &PgPool, job_ids: &[i32]) -> Result<Vec<Job>, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::fetch_jobs_by_ids");
+    let query = format!("{} WHERE id = ANY($1)", JOB_QUERY);
+    sqlx::query_as(&query).bind(job_ids).fetch_all(pool).await
+}
+
+/// Fetches every job with its timestamps, most recent first.
+pub async fn fetch_job_activity(pool: &PgPool) -> Result<Vec<JobActivity>, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::fetch_job_activity");
+    let query = format!(
+        r#"
+        SELECT hydrated.*, j.created_at, j.updated_at
+        FROM ({}) AS hydrated
+        JOIN jobs j ON j.id = hydrated.id
+        ORDER BY hydrated.start_date DESC, hydrated.id ASC
+        "#,
+        JOB_QUERY
+    );
+    sqlx::query_as(&query).fetch_all(pool).await
+}
+
+/// Fetches the ID and last modification time of every job, by ID.
+pub async fn fetch_job_dates(pool: &PgPool) -> Result<Vec<(i32, DateTime<Utc>)>, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::fetch_job_dates");
+    sqlx::query_as("SELECT id, updated_at FROM jobs ORDER BY id")
+        .fetch_all(pool)
+        .await
 }
 
 /// Fetches a single job by ID from the database.
@@ -58,13 +145,201 @@ pub async fn fetch_jobs(pool: &PgPool) -> Result<Vec<Job>, sqlx::Error> {
 ///
 /// # Returns
 ///
-/// * `Result<Option<Job>, sqlx::Error>` - The job if found, None if not found, or a database error
-pub async fn fetch_job_by_id(pool: &PgPool, job_id: i32) -> Result<Option<Job>, sqlx::Error> {
-    let row = sqlx::query(format!("{} WHERE id = $1", JOB_QUERY).as_str())
+/// * `Result<Option<T>, sqlx::Error>` - The job if found, None if not found, or a database error
+pub async fn fetch_job_by_id<T: DecodeRow>(
+    pool: &PgPool,
+    job_id: i32,
+) -> Result<Option<T>, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::fetch_job_by_id");
+    let row = sqlx::query_as(format!("{} WHERE id = $1", JOB_QUERY).as_str())
         .bind(job_id)
-        .map(map_row_to_job)
         .fetch_optional(pool)
         .await?;
 
     Ok(row)
 }
+
+const JOB_RETURNING: &str = r#"
+    RETURNING
+        id,
+        start_date,
+        end_date,
+        is_current_job,
+        company_name,
+        company_website,
+        description,
+        roles,
+        responsibilities
+"#;
+
+/// Inserts a new job into the database.
+///
+/// # Arguments
+///
+/// * `pool` - The database connection pool
+/// * `new_job` - The validated job payload to insert
+///
+/// # Returns
+///
+/// * `Result<Job, sqlx::Error>` - The created job including its generated ID, or a database error
+pub async fn create_job(pool: &PgPool, new_job: &NewJob) -> Result<Job, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::create_job");
+    let query = format!(
+        r#"
+        INSERT INTO jobs (
+            start_date,
+            end_date,
+            is_current_job,
+            company_name,
+            company_website,
+            description,
+            roles,
+            responsibilities
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        {}"#,
+        JOB_RETURNING
+    );
+    sqlx::query_as(&query)
+        .bind(new_job.start_date)
+        .bind(new_job.end_date)
+        .bind(new_job.is_current_job)
+        .bind(&new_job.company_name)
+        .bind(&new_job.company_website)
+        .bind(&new_job.description)
+        .bind(&new_job.roles)
+        .bind(&new_job.responsibilities)
+        .fetch_one(pool)
+        .await
+}
+
+/// Replaces every column of an existing job.
+///
+/// # Arguments
+///
+/// * `pool` - The database connection pool
+/// * `job_id` - The ID of the job to update
+/// * `job` - The validated replacement payload
+///
+/// # Returns
+///
+/// * `Result<Option<Job>, sqlx::Error>` - The updated job, None if no job has that ID, or a database error
+pub async fn update_job(
+    pool: &PgPool,
+    job_id: i32,
+    job: &NewJob,
+) -> Result<Option<Job>, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::update_job");
+    update_with(pool, job_id, job).await
+}
+
+/// Reasons a job patch can be refused besides a plain database failure
+#[derive(Debug)]
+pub enum JobWriteError {
+    /// The patched job is invalid, e.g. it ends before it starts
+    Invalid(String),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for JobWriteError {
+    fn from(e: sqlx::Error) -> Self {
+        JobWriteError::Database(e)
+    }
+}
+
+/// Merges a patch over an existing job and saves it.
+///
+/// The job is locked from the read to the write, so concurrent writes to it are never
+/// overwritten with stale values.
+///
+/// # Arguments
+///
+/// * `pool` - The database connection pool
+/// * `job_id` - The ID of the job to update
+/// * `patch` - The fields to change
+///
+/// # Returns
+///
+/// * `Result<Option<Job>, JobWriteError>` - The updated job, None if no job has that ID,
+///   or why the patched job was refused
+pub async fn patch_job(
+    pool: &PgPool,
+    job_id: i32,
+    patch: JobPatch,
+) -> Result<Option<Job>, JobWriteError> {
+    let _timer = QueryTimer::start("jobs_db::patch_job");
+    let mut tx = pool.begin().await?;
+
+    let existing: Option<Job> =
+        sqlx::query_as(format!("{} WHERE id = $1 FOR UPDATE", JOB_QUERY).as_str())
+            .bind(job_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+    let Some(existing) = existing else {
+        return Ok(None);
+    };
+
+    let job = patch.apply_to(existing);
+    job.validate().map_err(JobWriteError::Invalid)?;
+    let updated = update_with(&mut *tx, job_id, &job).await?;
+
+    tx.commit().await?;
+
+    Ok(updated)
+}
+
+/// Writes every column of a job, returning it if it exists.
+async fn update_with<'e>(
+    executor: impl PgExecutor<'e>,
+    job_id: i32,
+    job: &NewJob,
+) -> Result<Option<Job>, sqlx::Error> {
+    let query = format!(
+        r#"
+        UPDATE jobs SET
+            start_date = $2,
+            end_date = $3,
+            is_current_job = $4,
+            company_name = $5,
+            company_website = $6,
+            description = $7,
+            roles = $8,
+            responsibilities = $9
+        WHERE id = $1
+        {}"#,
+        JOB_RETURNING
+    );
+    sqlx::query_as(&query)
+        .bind(job_id)
+        .bind(job.start_date)
+        .bind(job.end_date)
+        .bind(job.is_current_job)
+        .bind(&job.company_name)
+        .bind(&job.company_website)
+        .bind(&job.description)
+        .bind(&job.roles)
+        .bind(&job.responsibilities)
+        .fetch_optional(executor)
+        .await
+}
+
+/// Deletes a job by ID.
+///
+/// # Arguments
+///
+/// * `pool` - The database connection pool
+/// * `job_id` - The ID of the job to delete
+///
+/// # Returns
+///
+/// * `Result<bool, sqlx::Error>` - Whether a job was deleted, or a database error
+///   (a foreign key violation if projects still reference the job)
+pub async fn delete_job(pool: &PgPool, job_id: i32) -> Result<bool, sqlx::Error> {
+    let _timer = QueryTimer::start("jobs_db::delete_job");
+    let result = sqlx::query("DELETE FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
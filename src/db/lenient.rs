@@ -0,0 +1,80 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sqlx::postgres::{PgRow, Postgres};
+use sqlx::{Decode, FromRow, Row, Type};
+use std::marker::PhantomData;
+use tracing::warn;
+
+/// A row decoded column by column instead of all-or-nothing.
+///
+/// Columns that fail to decode are returned as `null` and described in `warnings`,
+/// rather than failing the request or being replaced by made-up defaults.
+/// `T` is the model whose columns are decoded.
+#[derive(Serialize, Debug, Clone)]
+pub struct Lenient<T> {
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+    pub warnings: Vec<String>,
+    #[serde(skip)]
+    _model: PhantomData<T>,
+}
+
+/// Implemented for every model that can be served in lenient mode
+pub trait LenientDecode {
+    /// Decodes each column of the model from `row` into `decoder`
+    fn decode_columns(decoder: &mut LenientDecoder<'_>);
+}
+
+/// Accumulates decoded columns and decode warnings for a single row
+pub struct LenientDecoder<'r> {
+    row: &'r PgRow,
+    fields: Map<String, Value>,
+    warnings: Vec<String>,
+}
+
+impl<'r> LenientDecoder<'r> {
+    /// Decodes `column` as `V`, recording `null` and a warning if that fails
+    pub fn column<V>(&mut self, column: &str)
+    where
+        V: Decode<'r, Postgres> + Type<Postgres> + Serialize,
+    {
+        let value = self
+            .row
+            .try_get::<V, _>(column)
+            .map_err(|e| e.to_string())
+            .and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string()));
+
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Lenient decode of column {} failed: {}", column, e);
+                self.warnings.push(format!("{}: {}", column, e));
+                Value::Null
+            }
+        };
+        self.fields.insert(column.to_string(), value);
+    }
+}
+
+impl<'r, T: LenientDecode> FromRow<'r, PgRow> for Lenient<T> {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let mut decoder = LenientDecoder {
+            row,
+            fields: Map::new(),
+            warnings: Vec::new(),
+        };
+        T::decode_columns(&mut decoder);
+
+        Ok(Lenient {
+            fields: decoder.fields,
+            warnings: decoder.warnings,
+            _model: PhantomData,
+        })
+    }
+}
+
+/// Shorthand for the types a fetch function can decode rows into: a model for strict
+/// decoding, or `Lenient<Model>` for lenient decoding
+pub trait DecodeRow: for<'r> FromRow<'r, PgRow> + Send + Unpin {}
+
+impl<T> DecodeRow for T where T: for<'r> FromRow<'r, PgRow> + Send + Unpin {}
@@ -1,5 +1,10 @@
+pub mod api_tokens_db;
 pub mod connection;
 pub mod jobs_db;
+pub mod lenient;
+pub mod pagination;
 pub mod proficiency_enum;
 pub mod projects_db;
+pub mod search_db;
 pub mod skills_db;
+pub mod versions_db;
@@ -0,0 +1,193 @@
+use crate::db::lenient::DecodeRow;
+use crate::db::proficiency_enum::Proficiency;
+use crate::models::page::{Cursor, Page};
+use chrono::NaiveDate;
+use sqlx::{Error, PgPool, Postgres, QueryBuilder, Row};
+
+/// SQL type of a sort column, used to validate cursor values and cast them back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    Int,
+    Text,
+    Date,
+    Proficiency,
+}
+
+impl KeyType {
+    fn sql_type(self) -> &'static str {
+        match self {
+            KeyType::Int => "int",
+            KeyType::Text => "text",
+            KeyType::Date => "date",
+            KeyType::Proficiency => "proficiency",
+        }
+    }
+
+    /// Whether a cursor value can be cast to this type
+    pub fn accepts(self, value: &str) -> bool {
+        match self {
+            KeyType::Int => value.parse::<i32>().is_ok(),
+            KeyType::Text => true,
+            KeyType::Date => value.parse::<NaiveDate>().is_ok(),
+            KeyType::Proficiency => serde_json::from_value::<Proficiency>(value.into()).is_ok(),
+        }
+    }
+}
+
+/// A non-nullable column a listing may be sorted by
+#[derive(Debug)]
+pub struct SortKey {
+    /// Column name, as returned by the listing's base query and accepted by `?sort=`
+    pub name: &'static str,
+    pub key_type: KeyType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SortTerm {
+    pub key: &'static SortKey,
+    pub direction: Direction,
+}
+
+/// A validated request for one page of a listing
+#[derive(Debug)]
+pub struct PageRequest {
+    pub limit: i64,
+    /// Sort terms, always ending with a unique `id` tie-breaker
+    pub sort: Vec<SortTerm>,
+    /// Sort values of the last row of the previous page, one per sort term
+    pub after: Option<Vec<String>>,
+}
+
+impl PageRequest {
+    /// Canonical `?sort=` form of the sort terms, stored in cursors
+    pub fn sort_string(&self) -> String {
+        self.sort
+            .iter()
+            .map(|term| match term.direction {
+                Direction::Asc => term.key.name.to_string(),
+                Direction::Desc => format!("-{}", term.key.name),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// A keyset-paginated query over the rows of a base query.
+///
+/// The base query is wrapped as `page_rows`, so filters and sort keys refer to
+/// its output columns. Filters are added with [`Listing::filter`].
+pub struct Listing<'a> {
+    query: QueryBuilder<'a, Postgres>,
+    page: &'a PageRequest,
+}
+
+impl<'a> Listing<'a> {
+    pub fn new(base_query: &str, page: &'a PageRequest) -> Self {
+        Self::with_base(page, |query| {
+            query.push(base_query);
+        })
+    }
+
+    /// Like [`Listing::new`], but the base query is written by `build`, so it can bind
+    /// its own parameters.
+    pub fn with_base(
+        page: &'a PageRequest,
+        build: impl FnOnce(&mut QueryBuilder<'a, Postgres>),
+    ) -> Self {
+        let page_key = page
+            .sort
+            .iter()
+            .map(|term| format!("page_rows.{}::text", term.key.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut query = QueryBuilder::new(format!(
+            "SELECT page_rows.*, ARRAY[{}] AS page_key FROM (",
+            page_key
+        ));
+        build(&mut query);
+        query.push(") AS page_rows WHERE TRUE");
+
+        Listing { query, page }
+    }
+
+    /// Starts a new `AND` condition on the listing and returns the builder to write it
+    pub fn filter(&mut self) -> &mut QueryBuilder<'a, Postgres> {
+        self.query.push(" AND ");
+        &mut self.query
+    }
+
+    /// Fetches one page, reading one extra row to know whether another page follows.
+    pub async fn fetch<T: DecodeRow>(mut self, pool: &PgPool) -> Result<Page<T>, Error> {
+        let page = self.page;
+
+        if let Some(after) = &page.after {
+            // (a, b) after (x, y) in the sort order: a beyond x, or a = x and b beyond y
+            self.query.push(" AND (");
+            for (i, term) in page.sort.iter().enumerate() {
+                if i > 0 {
+                    self.query.push(" OR ");
+                }
+                self.query.push("(");
+                for (prior, value) in page.sort[..i].iter().zip(after) {
+                    self.push_comparison(prior, "=", value);
+                    self.query.push(" AND ");
+                }
+                let operator = match term.direction {
+                    Direction::Asc => ">",
+                    Direction::Desc => "<",
+                };
+                self.push_comparison(term, operator, &after[i]);
+                self.query.push(")");
+            }
+            self.query.push(")");
+        }
+
+        self.query.push(" ORDER BY ");
+        for (i, term) in page.sort.iter().enumerate() {
+            if i > 0 {
+                self.query.push(", ");
+            }
+            self.query.push(format!(
+                "page_rows.{} {}",
+                term.key.name,
+                match term.direction {
+                    Direction::Asc => "ASC",
+                    Direction::Desc => "DESC",
+                }
+            ));
+        }
+        self.query.push(" LIMIT ").push_bind(page.limit + 1);
+
+        let mut rows = self.query.build().fetch_all(pool).await?;
+
+        let next_cursor = if rows.len() as i64 > page.limit {
+            rows.truncate(page.limit as usize);
+            let last = rows.last().expect("limit is at least 1");
+            let after: Vec<String> = last.try_get("page_key")?;
+            Some(Cursor::new(page.sort_string(), after).encode())
+        } else {
+            None
+        };
+
+        let items = rows
+            .iter()
+            .map(T::from_row)
+            .collect::<Result<Vec<T>, Error>>()?;
+
+        Ok(Page { items, next_cursor })
+    }
+
+    fn push_comparison(&mut self, term: &SortTerm, operator: &str, value: &str) {
+        self.query
+            .push(format!("page_rows.{} {} ", term.key.name, operator))
+            .push_bind(value.to_string())
+            .push(format!("::{}", term.key.key_type.sql_type()));
+    }
+}
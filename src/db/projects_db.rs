@@ -1,12 +1,34 @@
-use crate::models::project::Project;
-use sqlx::PgPool;
-use sqlx::postgres::PgRow;
-use sqlx::{Error, Row};
+use crate::db::lenient::{DecodeRow, LenientDecode, LenientDecoder};
+use crate::db::pagination::{KeyType, Listing, PageRequest, SortKey};
+use crate::metrics::QueryTimer;
+use crate::models::feed::ProjectActivity;
+use crate::models::page::Page;
+use crate::models::project::{
+    MatchedProject, NewProject, Project, ProjectFilters, ProjectPatch, SkillMatch,
+};
+use crate::models::skill::MAX_HIERARCHY_DEPTH;
+use crate::models::skill::Skill;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{Error, FromRow};
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
 
+/// Projects with their skills as a JSON array, ordered by name.
+///
+/// Each project's skills are aggregated in a lateral subquery, so filters on `p`,
+/// whether appended here or applied to the query wrapped as a subquery, reach
+/// `projects` first and only the matching projects' skills are aggregated.
 const PROJECT_SKILLS_QUERY: &str = r#"
-    WITH project_skills AS (
+    SELECT
+        p.id,
+        p.name,
+        p.description,
+        p.github_url,
+        p.job_id,
+        project_skills.skills
+    FROM projects p
+    CROSS JOIN LATERAL (
         SELECT
-            p.id as project_id,
             COALESCE(
                 jsonb_agg(
                     jsonb_build_object(
@@ -16,79 +38,519 @@ const PROJECT_SKILLS_QUERY: &str = r#"
                         'official_site_url', s.official_site_url,
                         'proficiency', s.proficiency
                     ) ORDER BY s.name ASC
-                ) FILTER (WHERE s.id IS NOT NULL),
+                ),
                 '[]'::jsonb
-            ) as skills
-        FROM projects p
-        LEFT JOIN projects_skills ps ON p.id = ps.project_id
-        LEFT JOIN skills s ON ps.skill_id = s.id
-        GROUP BY p.id
-    )
-    SELECT
-        p.id,
-        p.name,
-        p.description,
-        p.github_url,
-        p.job_id,
-        COALESCE(ps.skills, '[]'::jsonb) as skills
-    FROM projects p
-    LEFT JOIN project_skills ps ON p.id = ps.project_id
+            ) AS skills
+        FROM projects_skills ps
+        JOIN skills s ON s.id = ps.skill_id
+        WHERE ps.project_id = p.id
+    ) project_skills
 "#;
 
-/// Maps a database row to a `Project` struct.
-fn map_row_to_project(row: PgRow) -> Project {
-    Project {
-        id: row.try_get("id").unwrap_or_default(),
-        name: row.try_get("name").unwrap_or_default(),
-        description: row.try_get("description").unwrap_or_default(),
-        github_url: row.try_get("github_url").unwrap_or_default(),
-        job_id: row.try_get("job_id").unwrap_or_default(),
-        skills: serde_json::from_value(
-            row.try_get::<serde_json::Value, _>("skills")
-                .unwrap_or_default(),
-        )
-        .unwrap_or_default(),
+/// Columns the project listings can be sorted by
+pub const PROJECT_SORT_KEYS: &[SortKey] = &[
+    SortKey {
+        name: "name",
+        key_type: KeyType::Text,
+    },
+    SortKey {
+        name: "id",
+        key_type: KeyType::Int,
+    },
+];
+
+pub const PROJECT_DEFAULT_SORT: &str = "id";
+
+impl LenientDecode for Project {
+    fn decode_columns(decoder: &mut LenientDecoder<'_>) {
+        decoder.column::<i32>("id");
+        decoder.column::<String>("name");
+        decoder.column::<String>("description");
+        decoder.column::<Option<String>>("github_url");
+        decoder.column::<Option<i32>>("job_id");
+        decoder.column::<Json<Vec<Skill>>>("skills");
     }
 }
 
-pub async fn fetch_projects(pool: &PgPool) -> Result<Vec<Project>, Error> {
-    let query = format!("{} ORDER BY id ASC", PROJECT_SKILLS_QUERY);
-    sqlx::query(&query)
-        .map(map_row_to_project)
-        .fetch_all(pool)
-        .await
+impl LenientDecode for MatchedProject {
+    fn decode_columns(decoder: &mut LenientDecoder<'_>) {
+        Project::decode_columns(decoder);
+        decoder.column::<Vec<i32>>("matched_skill_ids");
+    }
 }
 
-pub async fn fetch_project_by_id(pool: &PgPool, project_id: i32) -> Result<Option<Project>, Error> {
-    let query = format!("{} WHERE p.id = $1", PROJECT_SKILLS_QUERY);
-    sqlx::query(&query)
-        .bind(project_id)
-        .map(map_row_to_project)
-        .fetch_optional(pool)
-        .await
+pub async fn fetch_projects<T: DecodeRow>(
+    pool: &PgPool,
+    filters: &ProjectFilters,
+    page: &PageRequest,
+) -> Result<Page<T>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_projects");
+    let mut listing = Listing::new(PROJECT_SKILLS_QUERY, page);
+
+    let skill_names = filters.skill_names();
+    if !skill_names.is_empty() {
+        let required = match filters.skill_match {
+            SkillMatch::Any => 1,
+            SkillMatch::All => skill_names.len() as i64,
+        };
+        listing
+            .filter()
+            .push(
+                r#"(
+                SELECT COUNT(DISTINCT LOWER(s.name))
+                FROM projects_skills ps
+                JOIN skills s ON s.id = ps.skill_id
+                WHERE ps.project_id = page_rows.id AND LOWER(s.name) = ANY("#,
+            )
+            .push_bind(skill_names)
+            .push(")) >= ")
+            .push_bind(required);
+    }
+    if let Some(has_github) = filters.has_github {
+        listing
+            .filter()
+            .push("(COALESCE(page_rows.github_url, '') <> '') = ")
+            .push_bind(has_github);
+    }
+
+    listing.fetch(pool).await
+}
+
+/// Fetches every project in the default `/projects` order, for exports
+pub async fn fetch_all_projects(pool: &PgPool) -> Result<Vec<Project>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_all_projects");
+    let query = format!("{} ORDER BY p.id ASC", PROJECT_SKILLS_QUERY);
+    sqlx::query_as(&query).fetch_all(pool).await
+}
+
+/// Fetches the projects of the given jobs ordered by ID, for batched lookups
+pub async fn fetch_projects_by_job_ids(
+    pool: &PgPool,
+    job_ids: &[i32],
+) -> Result<Vec<Project>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_projects_by_job_ids");
+    let query = format!(
+        "{} WHERE p.job_id = ANY($1) ORDER BY p.id ASC",
+        PROJECT_SKILLS_QUERY
+    );
+    sqlx::query_as(&query).bind(job_ids).fetch_all(pool).await
 }
 
-pub async fn fetch_projects_by_job(pool: &PgPool, job_id: i32) -> Result<Vec<Project>, Error> {
+/// A project linked to one of the skills in a batched lookup
+#[derive(FromRow)]
+struct SkillProject {
+    skill_id: i32,
+    #[sqlx(flatten)]
+    project: Project,
+}
+
+/// Fetches the projects directly linked to each of the given skills ordered by ID, as
+/// (skill ID, project) pairs, for batched lookups
+pub async fn fetch_projects_by_skill_ids(
+    pool: &PgPool,
+    skill_ids: &[i32],
+) -> Result<Vec<(i32, Project)>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_projects_by_skill_ids");
     let query = format!(
-        "{} WHERE p.job_id = $1 ORDER BY id ASC",
+        r#"
+        SELECT links.skill_id, hydrated.*
+        FROM projects_skills links
+        JOIN ({}) AS hydrated ON hydrated.id = links.project_id
+        WHERE links.skill_id = ANY($1)
+        ORDER BY hydrated.id ASC
+        "#,
         PROJECT_SKILLS_QUERY
     );
-    sqlx::query(&query)
-        .bind(job_id)
-        .map(map_row_to_project)
+    let rows: Vec<SkillProject> = sqlx::query_as(&query)
+        .bind(skill_ids)
         .fetch_all(pool)
-        .await
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.skill_id, row.project))
+        .collect())
 }
 
-pub async fn fetch_projects_by_skill(pool: &PgPool, skill_id: i32) -> Result<Vec<Project>, Error> {
-    // Filter projects by skill (join on projects_skills mapping table)
+/// Fetches the most recently changed projects with their timestamps, newest first.
+pub async fn fetch_project_activity(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<ProjectActivity>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_project_activity");
     let query = format!(
-        "{} WHERE p.id IN (SELECT project_id FROM projects_skills WHERE skill_id = $1) ORDER BY id ASC",
+        r#"
+        SELECT hydrated.*, p.created_at, p.updated_at
+        FROM ({}) AS hydrated
+        JOIN projects p ON p.id = hydrated.id
+        ORDER BY p.updated_at DESC, p.id DESC
+        LIMIT $1
+        "#,
         PROJECT_SKILLS_QUERY
     );
-    sqlx::query(&query)
-        .bind(skill_id)
-        .map(map_row_to_project)
+    sqlx::query_as(&query).bind(limit).fetch_all(pool).await
+}
+
+/// Fetches a project with its timestamps.
+pub async fn fetch_project_activity_by_id(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Option<ProjectActivity>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_project_activity_by_id");
+    let query = format!(
+        r#"
+        SELECT hydrated.*, p.created_at, p.updated_at
+        FROM ({}) AS hydrated
+        JOIN projects p ON p.id = hydrated.id
+        WHERE p.id = $1
+        "#,
+        PROJECT_SKILLS_QUERY
+    );
+    sqlx::query_as(&query)
+        .bind(project_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Fetches the ID and last modification time of every project, by ID.
+pub async fn fetch_project_dates(pool: &PgPool) -> Result<Vec<(i32, DateTime<Utc>)>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_project_dates");
+    sqlx::query_as("SELECT id, updated_at FROM projects ORDER BY id")
         .fetch_all(pool)
         .await
 }
+
+pub async fn fetch_project_by_id<T: DecodeRow>(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Option<T>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_project_by_id");
+    fetch_project_with(pool, project_id).await
+}
+
+/// Fetches a hydrated project through any executor, so writes can re-read
+/// the project inside their own transaction.
+async fn fetch_project_with<'e, T: DecodeRow, E: PgExecutor<'e>>(
+    executor: E,
+    project_id: i32,
+) -> Result<Option<T>, Error> {
+    let query = format!("{} WHERE p.id = $1", PROJECT_SKILLS_QUERY);
+    sqlx::query_as(&query)
+        .bind(project_id)
+        .fetch_optional(executor)
+        .await
+}
+
+pub async fn fetch_projects_by_job<T: DecodeRow>(
+    pool: &PgPool,
+    job_id: i32,
+    page: &PageRequest,
+) -> Result<Page<T>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_projects_by_job");
+    let mut listing = Listing::new(PROJECT_SKILLS_QUERY, page);
+    listing
+        .filter()
+        .push("page_rows.job_id = ")
+        .push_bind(job_id);
+    listing.fetch(pool).await
+}
+
+/// Fetches the projects using a skill, each with the IDs of its skills that matched.
+///
+/// With `include_descendants`, projects using any sub-skill also match. Sub-skills
+/// are walked like `skills_db`'s descendants, stopping at any skill already visited.
+pub async fn fetch_projects_by_skill<T: DecodeRow>(
+    pool: &PgPool,
+    skill_id: i32,
+    include_descendants: bool,
+    page: &PageRequest,
+) -> Result<Page<T>, Error> {
+    let _timer = QueryTimer::start("projects_db::fetch_projects_by_skill");
+    let max_depth = if include_descendants {
+        MAX_HIERARCHY_DEPTH as i32
+    } else {
+        0
+    };
+
+    // Join projects to their skills in the requested subtree (via the projects_skills mapping table)
+    let listing = Listing::with_base(page, |query| {
+        query
+            .push("SELECT p.*, m.matched_skill_ids FROM (")
+            .push(PROJECT_SKILLS_QUERY)
+            .push(
+                r#") p
+                JOIN (
+                    WITH RECURSIVE subtree AS (
+                        SELECT id, 0 AS depth, ARRAY[id] AS path
+                        FROM skills
+                        WHERE id = "#,
+            )
+            .push_bind(skill_id)
+            .push(
+                r#"
+                        UNION ALL
+                        SELECT s.id, t.depth + 1, t.path || s.id
+                        FROM skills s
+                        JOIN subtree t ON s.parent_id = t.id
+                        WHERE t.depth < "#,
+            )
+            .push_bind(max_depth)
+            .push(
+                r#" AND NOT s.id = ANY(t.path)
+                    )
+                    SELECT
+                        ps.project_id,
+                        array_agg(ps.skill_id ORDER BY ps.skill_id) AS matched_skill_ids
+                    FROM projects_skills ps
+                    JOIN subtree ON subtree.id = ps.skill_id
+                    GROUP BY ps.project_id
+                ) m ON m.project_id = p.id"#,
+            );
+    });
+    listing.fetch(pool).await
+}
+
+/// Locks a project row for the rest of the transaction, returning whether it exists.
+async fn lock_project(tx: &mut Transaction<'_, Postgres>, project_id: i32) -> Result<bool, Error> {
+    let row = sqlx::query("SELECT id FROM projects WHERE id = $1 FOR UPDATE")
+        .bind(project_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+    Ok(row.is_some())
+}
+
+/// Replaces every skill link of a project with `skill_ids`.
+async fn replace_skill_links(
+    tx: &mut Transaction<'_, Postgres>,
+    project_id: i32,
+    skill_ids: &[i32],
+) -> Result<(), Error> {
+    sqlx::query("DELETE FROM projects_skills WHERE project_id = $1")
+        .bind(project_id)
+        .execute(&mut **tx)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO projects_skills (project_id, skill_id) SELECT DISTINCT $1, UNNEST($2::int[])",
+    )
+    .bind(project_id)
+    .bind(skill_ids)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Re-reads the hydrated project and commits the transaction.
+async fn commit_and_fetch(
+    mut tx: Transaction<'_, Postgres>,
+    project_id: i32,
+) -> Result<Option<Project>, Error> {
+    let project = fetch_project_with(&mut *tx, project_id).await?;
+    tx.commit().await?;
+    Ok(project)
+}
+
+/// Inserts a project together with its skill links in a single transaction.
+///
+/// Returns a foreign key violation if `job_id` or any skill ID does not exist, and
+/// `None` if the inserted project could not be read back.
+pub async fn create_project(
+    pool: &PgPool,
+    new_project: &NewProject,
+) -> Result<Option<Project>, Error> {
+    let _timer = QueryTimer::start("projects_db::create_project");
+    let mut tx = pool.begin().await?;
+
+    let project_id: i32 = sqlx::query_scalar(
+        r#"
+        INSERT INTO projects (name, description, github_url, job_id)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+    )
+    .bind(&new_project.name)
+    .bind(&new_project.description)
+    .bind(&new_project.github_url)
+    .bind(new_project.job_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    replace_skill_links(&mut tx, project_id, &new_project.skill_ids).await?;
+
+    commit_and_fetch(tx, project_id).await
+}
+
+/// Replaces a project's columns and skill links in a single transaction.
+///
+/// Returns `None` if no project has the given ID.
+pub async fn update_project(
+    pool: &PgPool,
+    project_id: i32,
+    project: &NewProject,
+) -> Result<Option<Project>, Error> {
+    let _timer = QueryTimer::start("projects_db::update_project");
+    let mut tx = pool.begin().await?;
+
+    if !update_columns(&mut tx, project_id, project).await? {
+        return Ok(None);
+    }
+
+    replace_skill_links(&mut tx, project_id, &project.skill_ids).await?;
+
+    commit_and_fetch(tx, project_id).await
+}
+
+/// Merges a patch over the project and saves it in a single transaction. The project
+/// stays locked from the read to the write, so concurrent writes to it, including
+/// skill link changes, are never overwritten with stale values. Skill links are only
+/// replaced when the patch has `skill_ids`.
+///
+/// Returns `None` if no project has the given ID.
+pub async fn patch_project(
+    pool: &PgPool,
+    project_id: i32,
+    patch: ProjectPatch,
+) -> Result<Option<Project>, Error> {
+    let _timer = QueryTimer::start("projects_db::patch_project");
+    let mut tx = pool.begin().await?;
+
+    if !lock_project(&mut tx, project_id).await? {
+        return Ok(None);
+    }
+    let Some(existing) = fetch_project_with(&mut *tx, project_id).await? else {
+        return Ok(None);
+    };
+
+    let replaces_skills = patch.skill_ids.is_some();
+    let project = patch.apply_to(existing);
+    update_columns(&mut tx, project_id, &project).await?;
+    if replaces_skills {
+        replace_skill_links(&mut tx, project_id, &project.skill_ids).await?;
+    }
+
+    commit_and_fetch(tx, project_id).await
+}
+
+/// Writes a project's columns, returning whether the project exists.
+async fn update_columns(
+    tx: &mut Transaction<'_, Postgres>,
+    project_id: i32,
+    project: &NewProject,
+) -> Result<bool, Error> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE projects SET
+            name = $2,
+            description = $3,
+            github_url = $4,
+            job_id = $5
+        WHERE id = $1
+        "#,
+    )
+    .bind(project_id)
+    .bind(&project.name)
+    .bind(&project.description)
+    .bind(&project.github_url)
+    .bind(project.job_id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(updated.rows_affected() > 0)
+}
+
+/// Deletes a project and its skill links, returning whether the project existed.
+pub async fn delete_project(pool: &PgPool, project_id: i32) -> Result<bool, Error> {
+    let _timer = QueryTimer::start("projects_db::delete_project");
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM projects_skills WHERE project_id = $1")
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let deleted = sqlx::query("DELETE FROM projects WHERE id = $1")
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(deleted.rows_affected() > 0)
+}
+
+/// Replaces the full set of skills linked to a project.
+///
+/// Returns `None` if no project has the given ID.
+pub async fn replace_project_skills(
+    pool: &PgPool,
+    project_id: i32,
+    skill_ids: &[i32],
+) -> Result<Option<Project>, Error> {
+    let _timer = QueryTimer::start("projects_db::replace_project_skills");
+    let mut tx = pool.begin().await?;
+
+    if !lock_project(&mut tx, project_id).await? {
+        return Ok(None);
+    }
+
+    replace_skill_links(&mut tx, project_id, skill_ids).await?;
+
+    commit_and_fetch(tx, project_id).await
+}
+
+/// Links a single skill to a project; linking an already linked skill is a no-op.
+///
+/// Returns `None` if no project has the given ID.
+pub async fn add_project_skill(
+    pool: &PgPool,
+    project_id: i32,
+    skill_id: i32,
+) -> Result<Option<Project>, Error> {
+    let _timer = QueryTimer::start("projects_db::add_project_skill");
+    let mut tx = pool.begin().await?;
+
+    if !lock_project(&mut tx, project_id).await? {
+        return Ok(None);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO projects_skills (project_id, skill_id)
+        SELECT $1, $2
+        WHERE NOT EXISTS (
+            SELECT 1 FROM projects_skills WHERE project_id = $1 AND skill_id = $2
+        )
+        "#,
+    )
+    .bind(project_id)
+    .bind(skill_id)
+    .execute(&mut *tx)
+    .await?;
+
+    commit_and_fetch(tx, project_id).await
+}
+
+/// Unlinks a single skill from a project; unlinking a skill that is not linked is a no-op.
+///
+/// Returns `None` if no project has the given ID.
+pub async fn remove_project_skill(
+    pool: &PgPool,
+    project_id: i32,
+    skill_id: i32,
+) -> Result<Option<Project>, Error> {
+    let _timer = QueryTimer::start("projects_db::remove_project_skill");
+    let mut tx = pool.begin().await?;
+
+    if !lock_project(&mut tx, project_id).await? {
+        return Ok(None);
+    }
+
+    sqlx::query("DELETE FROM projects_skills WHERE project_id = $1 AND skill_id = $2")
+        .bind(project_id)
+        .bind(skill_id)
+        .execute(&mut *tx)
+        .await?;
+
+    commit_and_fetch(tx, project_id).await
+}
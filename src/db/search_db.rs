@@ -0,0 +1,109 @@
+use crate::metrics::QueryTimer;
+use crate::models::search::SearchHit;
+use sqlx::{Error, PgPool};
+
+/// Marks the start and end of a match in `ts_headline` output. Control characters are
+/// stripped from documents first, so they only ever come from `ts_headline`.
+const START_SEL: char = '\u{2}';
+const STOP_SEL: char = '\u{3}';
+
+/// Ranks matches from every searchable table against one `websearch_to_tsquery`.
+/// `ts_rank_cd` is normalized by document length (32 maps it into 0..1) so long
+/// job descriptions do not drown out short project and skill names.
+///
+/// Matches are delimited with [`START_SEL`] and [`STOP_SEL`] rather than HTML, since
+/// the document text is not escaped; [`highlight_html`] escapes it afterwards.
+const SEARCH_QUERY: &str = r#"
+    WITH query AS (
+        SELECT websearch_to_tsquery('english', $1) AS tsq
+    ),
+    hits AS (
+        SELECT
+            'project' AS kind,
+            p.id,
+            ts_rank_cd(p.search_vector, query.tsq, 32) AS score,
+            concat_ws(' - ', p.name, p.description) AS document
+        FROM projects p, query
+        WHERE p.search_vector @@ query.tsq
+        UNION ALL
+        SELECT
+            'job' AS kind,
+            j.id,
+            ts_rank_cd(j.search_vector, query.tsq, 32) AS score,
+            concat_ws(' - ', j.company_name, j.roles, j.description, j.responsibilities) AS document
+        FROM jobs j, query
+        WHERE j.search_vector @@ query.tsq
+        UNION ALL
+        SELECT
+            'skill' AS kind,
+            s.id,
+            ts_rank_cd(s.search_vector, query.tsq, 32) AS score,
+            concat_ws(' - ', s.name, s.description) AS document
+        FROM skills s, query
+        WHERE s.search_vector @@ query.tsq
+    ),
+    top_hits AS (
+        SELECT * FROM hits
+        ORDER BY score DESC, kind ASC, id ASC
+        LIMIT $2
+    )
+    SELECT
+        top_hits.kind,
+        top_hits.id,
+        top_hits.score,
+        ts_headline(
+            'english',
+            translate(top_hits.document, chr(2) || chr(3), ''),
+            query.tsq,
+            'StartSel=' || chr(2) || ', StopSel=' || chr(3) || ', MaxWords=30, MinWords=10'
+        ) AS highlight
+    FROM top_hits, query
+    ORDER BY top_hits.score DESC, top_hits.kind ASC, top_hits.id ASC
+"#;
+
+/// Searches projects, jobs and skills, best matches first.
+///
+/// Snippets are only generated for the returned hits, since `ts_headline` re-parses
+/// the whole document.
+///
+/// # Arguments
+///
+/// * `pool` - The database connection pool
+/// * `query` - Web-search style query text
+/// * `limit` - Maximum number of hits to return
+///
+/// # Returns
+///
+/// * `Result<Vec<SearchHit>, Error>` - The ranked hits, or a database error
+pub async fn search(pool: &PgPool, query: &str, limit: i64) -> Result<Vec<SearchHit>, Error> {
+    let _timer = QueryTimer::start("search_db::search");
+    let mut hits: Vec<SearchHit> = sqlx::query_as(SEARCH_QUERY)
+        .bind(query)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+    for hit in &mut hits {
+        hit.highlight = highlight_html(&hit.highlight);
+    }
+    Ok(hits)
+}
+
+/// HTML-escapes a `ts_headline` snippet and wraps its matches in `<mark>` tags, so the
+/// snippet is safe to insert as HTML whatever the stored text contains.
+fn highlight_html(snippet: &str) -> String {
+    let mut html = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            START_SEL => html.push_str("<mark>"),
+            STOP_SEL => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#x27;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
@@ -1,9 +1,12 @@
+use crate::db::lenient::{DecodeRow, LenientDecode, LenientDecoder};
+use crate::db::pagination::{KeyType, Listing, PageRequest, SortKey};
 use crate::db::proficiency_enum::Proficiency;
-use crate::models::skill::Skill;
-use sqlx::PgPool;
-use sqlx::postgres::PgRow;
-use sqlx::{Error, Row};
-use tracing::error;
+use crate::metrics::QueryTimer;
+use crate::models::page::Page;
+use crate::models::skill::{NewSkill, RelatedSkill, Skill, SkillFilters, SkillNode, SkillPatch};
+use sqlx::Error;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
 
 const SKILL_QUERY: &str = r#"
     SELECT 
@@ -16,44 +19,549 @@ const SKILL_QUERY: &str = r#"
     FROM skills
 "#;
 
-fn map_row_to_skill(row: PgRow) -> Skill {
-    let proficiency = match row.try_get::<Proficiency, _>("proficiency") {
-        Ok(p) => p,
-        Err(e) => {
-            error!("Failed to get proficiency from database row: {:?}", e);
-            error!(
-                "Raw proficiency value: {:?}",
-                row.try_get::<String, _>("proficiency")
-            );
-            Proficiency::Beginner
-        }
-    };
+/// Columns `/skills` can be sorted by
+pub const SKILL_SORT_KEYS: &[SortKey] = &[
+    SortKey {
+        name: "name",
+        key_type: KeyType::Text,
+    },
+    SortKey {
+        name: "proficiency",
+        key_type: KeyType::Proficiency,
+    },
+    SortKey {
+        name: "id",
+        key_type: KeyType::Int,
+    },
+];
 
-    Skill {
-        id: row.try_get("id").unwrap_or_default(),
-        name: row.try_get("name").unwrap_or_default(),
-        description: row.try_get("description").unwrap_or_default(),
-        official_site_url: row.try_get("official_site_url").unwrap_or_default(),
-        proficiency,
-        parent_id: row.try_get("parent_id").unwrap_or_default(),
+pub const SKILL_DEFAULT_SORT: &str = "id";
+
+impl LenientDecode for Skill {
+    fn decode_columns(decoder: &mut LenientDecoder<'_>) {
+        decoder.column::<i32>("id");
+        decoder.column::<String>("name");
+        decoder.column::<String>("description");
+        decoder.column::<String>("official_site_url");
+        decoder.column::<Proficiency>("proficiency");
+        decoder.column::<Option<i32>>("parent_id");
     }
 }
 
-/// Implements the `SkillsRepository` trait for `PgPool` (used in production)
+// Implements the `SkillsRepository` trait for `PgPool` (used in production)
 
-pub async fn fetch_skills(pool: &PgPool) -> Result<Vec<Skill>, Error> {
-    let query = format!("{} ORDER BY id ASC", SKILL_QUERY);
-    sqlx::query(&query)
-        .map(map_row_to_skill)
+pub async fn fetch_skills<T: DecodeRow>(
+    pool: &PgPool,
+    filters: &SkillFilters,
+    page: &PageRequest,
+) -> Result<Page<T>, Error> {
+    let _timer = QueryTimer::start("skills_db::fetch_skills");
+    let mut listing = Listing::new(SKILL_QUERY, page);
+    if let Some(proficiency_min) = &filters.proficiency_min {
+        listing
+            .filter()
+            .push("page_rows.proficiency >= ")
+            .push_bind(proficiency_min.clone());
+    }
+    if let Some(parent_id) = filters.parent_id {
+        listing
+            .filter()
+            .push("page_rows.parent_id = ")
+            .push_bind(parent_id);
+    }
+    listing.fetch(pool).await
+}
+
+/// Fetches every skill ordered by name, for exports
+pub async fn fetch_all_skills(pool: &PgPool) -> Result<Vec<Skill>, Error> {
+    let _timer = QueryTimer::start("skills_db::fetch_all_skills");
+    let query = format!("{} ORDER BY name ASC, id ASC", SKILL_QUERY);
+    sqlx::query_as(&query).fetch_all(pool).await
+}
+
+/// Fetches the skills with the given IDs, for batched lookups
+pub async fn fetch_skills_by_ids(pool: &PgPool, skill_ids: &[i32]) -> Result<Vec<Skill>, Error> {
+    let _timer = QueryTimer::start("skills_db::fetch_skills_by_ids");
+    let query = format!("{} WHERE id = ANY($1)", SKILL_QUERY);
+    sqlx::query_as(&query).bind(skill_ids).fetch_all(pool).await
+}
+
+/// Fetches the children of the given skills ordered by name, for batched lookups
+pub async fn fetch_skills_by_parent_ids(
+    pool: &PgPool,
+    parent_ids: &[i32],
+) -> Result<Vec<Skill>, Error> {
+    let _timer = QueryTimer::start("skills_db::fetch_skills_by_parent_ids");
+    let query = format!(
+        "{} WHERE parent_id = ANY($1) ORDER BY name ASC, id ASC",
+        SKILL_QUERY
+    );
+    sqlx::query_as(&query)
+        .bind(parent_ids)
         .fetch_all(pool)
         .await
 }
 
-pub async fn fetch_skill_by_id(pool: &PgPool, skill_id: i32) -> Result<Option<Skill>, Error> {
+pub async fn fetch_skill_by_id<T: DecodeRow>(
+    pool: &PgPool,
+    skill_id: i32,
+) -> Result<Option<T>, Error> {
+    let _timer = QueryTimer::start("skills_db::fetch_skill_by_id");
     let query = format!("{} WHERE id = $1", SKILL_QUERY);
-    sqlx::query(&query)
+    sqlx::query_as(&query)
         .bind(skill_id)
-        .map(map_row_to_skill)
         .fetch_optional(pool)
         .await
 }
+
+/// Walks `parent_id` links up from skill `$1`, at most `$2` levels. The path array
+/// stops the walk at a skill already visited, so a corrupt cycle cannot loop.
+const ANCESTORS_QUERY: &str = r#"
+    WITH RECURSIVE ancestors AS (
+        SELECT id, parent_id, 0 AS depth, ARRAY[id] AS path
+        FROM skills
+        WHERE id = $1
+        UNION ALL
+        SELECT s.id, s.parent_id, a.depth + 1, a.path || s.id
+        FROM skills s
+        JOIN ancestors a ON s.id = a.parent_id
+        WHERE a.depth < $2 AND NOT s.id = ANY(a.path)
+    )
+    SELECT
+        s.id,
+        s.name,
+        s.description,
+        s.official_site_url,
+        s.proficiency,
+        s.parent_id,
+        a.depth
+    FROM ancestors a
+    JOIN skills s ON s.id = a.id
+    ORDER BY a.depth ASC
+"#;
+
+/// Walks `parent_id` links down from skill `$1`, at most `$2` levels, with the same
+/// cycle protection as [`ANCESTORS_QUERY`].
+const DESCENDANTS_QUERY: &str = r#"
+    WITH RECURSIVE descendants AS (
+        SELECT id, 0 AS depth, ARRAY[id] AS path
+        FROM skills
+        WHERE id = $1
+        UNION ALL
+        SELECT s.id, d.depth + 1, d.path || s.id
+        FROM skills s
+        JOIN descendants d ON s.parent_id = d.id
+        WHERE d.depth < $2 AND NOT s.id = ANY(d.path)
+    )
+    SELECT
+        s.id,
+        s.name,
+        s.description,
+        s.official_site_url,
+        s.proficiency,
+        s.parent_id,
+        d.depth
+    FROM descendants d
+    JOIN skills s ON s.id = d.id
+    ORDER BY d.depth ASC, s.name ASC
+"#;
+
+/// Walks down from every top-level skill, at most `$1` levels below them
+const TREE_QUERY: &str = r#"
+    WITH RECURSIVE tree AS (
+        SELECT id, 0 AS depth, ARRAY[id] AS path
+        FROM skills
+        WHERE parent_id IS NULL
+        UNION ALL
+        SELECT s.id, t.depth + 1, t.path || s.id
+        FROM skills s
+        JOIN tree t ON s.parent_id = t.id
+        WHERE t.depth < $1 AND NOT s.id = ANY(t.path)
+    )
+    SELECT
+        s.id,
+        s.name,
+        s.description,
+        s.official_site_url,
+        s.proficiency,
+        s.parent_id,
+        t.depth
+    FROM tree t
+    JOIN skills s ON s.id = t.id
+    ORDER BY s.name ASC
+"#;
+
+/// Fetches the ancestors of a skill, nearest parent first.
+///
+/// # Returns
+///
+/// * `Result<Option<Vec<RelatedSkill>>, Error>` - The ancestors, None if the skill does
+///   not exist, or a database error
+pub async fn fetch_skill_ancestors(
+    pool: &PgPool,
+    skill_id: i32,
+    max_depth: i32,
+) -> Result<Option<Vec<RelatedSkill>>, Error> {
+    let _timer = QueryTimer::start("skills_db::fetch_skill_ancestors");
+    related_skills(pool, ANCESTORS_QUERY, skill_id, max_depth).await
+}
+
+/// Fetches the descendants of a skill, level by level and by name within a level.
+///
+/// # Returns
+///
+/// * `Result<Option<Vec<RelatedSkill>>, Error>` - The descendants, None if the skill
+///   does not exist, or a database error
+pub async fn fetch_skill_descendants(
+    pool: &PgPool,
+    skill_id: i32,
+    max_depth: i32,
+) -> Result<Option<Vec<RelatedSkill>>, Error> {
+    let _timer = QueryTimer::start("skills_db::fetch_skill_descendants");
+    related_skills(pool, DESCENDANTS_QUERY, skill_id, max_depth).await
+}
+
+/// Runs a hierarchy walk whose first row is the starting skill at depth 0
+async fn related_skills(
+    pool: &PgPool,
+    query: &str,
+    skill_id: i32,
+    max_depth: i32,
+) -> Result<Option<Vec<RelatedSkill>>, Error> {
+    let rows: Vec<RelatedSkill> = sqlx::query_as(query)
+        .bind(skill_id)
+        .bind(max_depth)
+        .fetch_all(pool)
+        .await?;
+
+    if rows.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(rows.into_iter().filter(|row| row.depth > 0).collect()))
+}
+
+/// Fetches every skill reachable from a top-level skill as a nested tree, siblings
+/// ordered by name.
+///
+/// Skills deeper than `max_depth` levels below a top-level skill are left out, as
+/// are skills caught in a `parent_id` cycle, since no top-level skill reaches them.
+pub async fn fetch_skill_tree(pool: &PgPool, max_depth: i32) -> Result<Vec<SkillNode>, Error> {
+    let _timer = QueryTimer::start("skills_db::fetch_skill_tree");
+    let rows: Vec<RelatedSkill> = sqlx::query_as(TREE_QUERY)
+        .bind(max_depth)
+        .fetch_all(pool)
+        .await?;
+
+    let mut children: HashMap<Option<i32>, Vec<Skill>> = HashMap::new();
+    for row in rows {
+        let parent_id = if row.depth == 0 {
+            None
+        } else {
+            row.skill.parent_id
+        };
+        children.entry(parent_id).or_default().push(row.skill);
+    }
+
+    Ok(build_nodes(None, &mut children))
+}
+
+/// Takes the children of `parent_id` out of `children` and nests their own children
+fn build_nodes(
+    parent_id: Option<i32>,
+    children: &mut HashMap<Option<i32>, Vec<Skill>>,
+) -> Vec<SkillNode> {
+    children
+        .remove(&parent_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|skill| {
+            let nested = build_nodes(Some(skill.id), children);
+            SkillNode {
+                skill,
+                children: nested,
+            }
+        })
+        .collect()
+}
+
+const SKILL_RETURNING: &str = r#"
+    RETURNING
+        id,
+        name,
+        description,
+        official_site_url,
+        proficiency,
+        parent_id
+"#;
+
+/// Reasons a skill write can be refused besides a plain database failure
+#[derive(Debug)]
+pub enum SkillWriteError {
+    /// The patched skill is invalid, e.g. its name is empty
+    Invalid(String),
+    /// The requested `parent_id` does not exist
+    ParentNotFound(i32),
+    /// The requested `parent_id` is the skill itself or one of its descendants
+    ParentCycle(i32),
+    /// The skill is still linked to projects or has child skills
+    InUse {
+        projects: i64,
+        children: i64,
+    },
+    Database(Error),
+}
+
+impl From<Error> for SkillWriteError {
+    fn from(e: Error) -> Self {
+        SkillWriteError::Database(e)
+    }
+}
+
+/// Ensures `parent_id` exists and, when re-parenting an existing skill, that it is
+/// neither the skill itself nor one of its descendants.
+async fn check_parent(
+    tx: &mut Transaction<'_, Postgres>,
+    skill_id: Option<i32>,
+    parent_id: i32,
+) -> Result<(), SkillWriteError> {
+    // Walk up from the proposed parent; reaching `skill_id` means the change would
+    // close a loop. UNION (rather than UNION ALL) stops on any pre-existing cycle.
+    let ancestor_ids: Vec<i32> = sqlx::query_scalar(
+        r#"
+        WITH RECURSIVE ancestors AS (
+            SELECT id, parent_id FROM skills WHERE id = $1
+            UNION
+            SELECT s.id, s.parent_id
+            FROM skills s
+            JOIN ancestors a ON s.id = a.parent_id
+        )
+        SELECT id FROM ancestors
+        "#,
+    )
+    .bind(parent_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    if ancestor_ids.is_empty() {
+        return Err(SkillWriteError::ParentNotFound(parent_id));
+    }
+
+    if skill_id.is_some_and(|skill_id| ancestor_ids.contains(&skill_id)) {
+        return Err(SkillWriteError::ParentCycle(parent_id));
+    }
+
+    Ok(())
+}
+
+/// Inserts a new skill after checking that its parent exists.
+pub async fn create_skill(pool: &PgPool, new_skill: &NewSkill) -> Result<Skill, SkillWriteError> {
+    let _timer = QueryTimer::start("skills_db::create_skill");
+    let mut tx = pool.begin().await?;
+
+    // Keep the parent from being deleted between the check and the insert
+    lock_skills(&mut tx).await?;
+
+    if let Some(parent_id) = new_skill.parent_id {
+        check_parent(&mut tx, None, parent_id).await?;
+    }
+
+    let query = format!(
+        r#"
+        INSERT INTO skills (name, description, official_site_url, proficiency, parent_id)
+        VALUES ($1, $2, $3, $4, $5)
+        {}"#,
+        SKILL_RETURNING
+    );
+    let skill = sqlx::query_as(&query)
+        .bind(&new_skill.name)
+        .bind(&new_skill.description)
+        .bind(&new_skill.official_site_url)
+        .bind(&new_skill.proficiency)
+        .bind(new_skill.parent_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(skill)
+}
+
+/// Replaces every column of an existing skill, refusing parent changes that would
+/// create a cycle in the hierarchy.
+///
+/// Returns `None` if no skill has the given ID.
+pub async fn update_skill(
+    pool: &PgPool,
+    skill_id: i32,
+    skill: &NewSkill,
+) -> Result<Option<Skill>, SkillWriteError> {
+    let _timer = QueryTimer::start("skills_db::update_skill");
+    let mut tx = pool.begin().await?;
+
+    // Serialize concurrent re-parenting so two updates cannot form a cycle together
+    lock_skills(&mut tx).await?;
+    let updated = update_locked(&mut tx, skill_id, skill).await?;
+
+    tx.commit().await?;
+
+    Ok(updated)
+}
+
+/// Merges a patch over an existing skill and saves it, refusing parent changes that
+/// would create a cycle in the hierarchy. The skills stay locked from the read to the
+/// write, so concurrent writes are never overwritten with stale values.
+///
+/// Returns `None` if no skill has the given ID.
+pub async fn patch_skill(
+    pool: &PgPool,
+    skill_id: i32,
+    patch: SkillPatch,
+) -> Result<Option<Skill>, SkillWriteError> {
+    let _timer = QueryTimer::start("skills_db::patch_skill");
+    let mut tx = pool.begin().await?;
+
+    lock_skills(&mut tx).await?;
+    let existing: Option<Skill> = sqlx::query_as(&format!("{} WHERE id = $1", SKILL_QUERY))
+        .bind(skill_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    let Some(existing) = existing else {
+        return Ok(None);
+    };
+
+    let skill = patch.apply_to(existing);
+    skill.validate().map_err(SkillWriteError::Invalid)?;
+    let updated = update_locked(&mut tx, skill_id, &skill).await?;
+
+    tx.commit().await?;
+
+    Ok(updated)
+}
+
+/// Takes the lock every skill write holds while it checks the hierarchy, so checks
+/// and writes of concurrent transactions cannot interleave.
+async fn lock_skills(tx: &mut Transaction<'_, Postgres>) -> Result<(), Error> {
+    sqlx::query("LOCK TABLE skills IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
+
+/// Checks the new parent and writes every column of a skill, under [`lock_skills`].
+async fn update_locked(
+    tx: &mut Transaction<'_, Postgres>,
+    skill_id: i32,
+    skill: &NewSkill,
+) -> Result<Option<Skill>, SkillWriteError> {
+    if let Some(parent_id) = skill.parent_id {
+        check_parent(tx, Some(skill_id), parent_id).await?;
+    }
+
+    let query = format!(
+        r#"
+        UPDATE skills SET
+            name = $2,
+            description = $3,
+            official_site_url = $4,
+            proficiency = $5,
+            parent_id = $6
+        WHERE id = $1
+        {}"#,
+        SKILL_RETURNING
+    );
+    let updated = sqlx::query_as(&query)
+        .bind(skill_id)
+        .bind(&skill.name)
+        .bind(&skill.description)
+        .bind(&skill.official_site_url)
+        .bind(&skill.proficiency)
+        .bind(skill.parent_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+    Ok(updated)
+}
+
+/// Deletes a skill.
+///
+/// Without `cascade`, a skill still linked to projects or with child skills is refused
+/// with [`SkillWriteError::InUse`]. With `cascade`, the skill is deleted together with
+/// all of its descendants and every project link to any of them.
+///
+/// Returns whether the skill existed.
+pub async fn delete_skill(
+    pool: &PgPool,
+    skill_id: i32,
+    cascade: bool,
+) -> Result<bool, SkillWriteError> {
+    let _timer = QueryTimer::start("skills_db::delete_skill");
+    let mut tx = pool.begin().await?;
+
+    // Keep child skills from being added under the subtree while it is checked and
+    // deleted
+    lock_skills(&mut tx).await?;
+
+    let subtree_ids: Vec<i32> = sqlx::query_scalar(
+        r#"
+        WITH RECURSIVE subtree AS (
+            SELECT id FROM skills WHERE id = $1
+            UNION
+            SELECT s.id
+            FROM skills s
+            JOIN subtree t ON s.parent_id = t.id
+        )
+        SELECT id FROM subtree
+        "#,
+    )
+    .bind(skill_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    if subtree_ids.is_empty() {
+        return Ok(false);
+    }
+
+    // Wait for, then block, project links to the subtree, which the table lock allows
+    sqlx::query("SELECT id FROM skills WHERE id = ANY($1) FOR UPDATE")
+        .bind(&subtree_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    if !cascade {
+        let (projects, children): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM projects_skills WHERE skill_id = $1),
+                (SELECT COUNT(*) FROM skills WHERE parent_id = $1)
+            "#,
+        )
+        .bind(skill_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if projects > 0 || children > 0 {
+            return Err(SkillWriteError::InUse { projects, children });
+        }
+    }
+
+    sqlx::query("DELETE FROM projects_skills WHERE skill_id = ANY($1)")
+        .bind(&subtree_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    // Detach the subtree first so rows can be removed regardless of parent/child order
+    sqlx::query("UPDATE skills SET parent_id = NULL WHERE id = ANY($1)")
+        .bind(&subtree_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM skills WHERE id = ANY($1)")
+        .bind(&subtree_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
@@ -0,0 +1,37 @@
+use crate::metrics::QueryTimer;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// Change counters of a set of tables, maintained by the `bump_table_version` trigger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataVersion {
+    /// `table:version` pairs in table order, e.g. `jobs:3,skills:7`
+    pub versions: String,
+    /// When any of the tables was last written to
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Reads the current change counters of `tables`.
+pub async fn fetch_data_version(
+    pool: &PgPool,
+    tables: &[&str],
+) -> Result<DataVersion, sqlx::Error> {
+    let _timer = QueryTimer::start("versions_db::fetch_data_version");
+    let (versions, last_modified): (Option<String>, Option<DateTime<Utc>>) = sqlx::query_as(
+        r#"
+        SELECT
+            string_agg(table_name || ':' || version, ',' ORDER BY table_name),
+            max(updated_at)
+        FROM table_versions
+        WHERE table_name = ANY($1)
+        "#,
+    )
+    .bind(tables)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(DataVersion {
+        versions: versions.unwrap_or_default(),
+        last_modified: last_modified.unwrap_or(DateTime::UNIX_EPOCH),
+    })
+}
@@ -0,0 +1,407 @@
+use crate::metrics::Untracked;
+use crate::render::Themes;
+use crate::state::AppState;
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tower::ServiceExt;
+use tracing::{info, warn};
+
+/// Time the served snapshot was taken, sent with every stale response
+pub const X_DATA_STALE: HeaderName = HeaderName::from_static("x-data-stale");
+
+/// `Warning` sent with every stale response (RFC 7234, warn-code 110)
+const STALE_WARNING: &str = "110 - \"Response is Stale\"";
+
+/// Response headers kept in the snapshot; everything else is recomputed or irrelevant
+const KEPT_HEADERS: [HeaderName; 3] = [
+    header::CONTENT_TYPE,
+    header::CONTENT_DISPOSITION,
+    header::LINK,
+];
+
+/// Delays between attempts to reach the database while it is down
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// One GET response as stored in the snapshot
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StoredResponse {
+    pub headers: BTreeMap<String, String>,
+    /// Base64-encoded body
+    pub body: String,
+}
+
+/// Last known-good GET responses for every job, project and skill, keyed by request URI
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Snapshot {
+    pub taken_at: DateTime<Utc>,
+    pub responses: BTreeMap<String, StoredResponse>,
+}
+
+impl Snapshot {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read(path)
+            .map_err(|e| format!("Failed to read snapshot {}: {}", path.display(), e))?;
+        serde_json::from_slice(&contents)
+            .map_err(|e| format!("Failed to parse snapshot {}: {}", path.display(), e))
+    }
+
+    /// Writes the snapshot next to `path` and renames it into place, so a crash never
+    /// leaves a truncated file behind.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_vec(self).expect("snapshot serializes to JSON");
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, json)
+            .and_then(|()| std::fs::rename(&temp, path))
+            .map_err(|e| format!("Failed to write snapshot {}: {}", path.display(), e))
+    }
+}
+
+struct Inner {
+    path: Option<PathBuf>,
+    snapshot: RwLock<Option<Arc<Snapshot>>>,
+    /// Whether the database is believed to be reachable
+    available: watch::Sender<bool>,
+}
+
+/// Serving of the last known-good data while the database is down.
+///
+/// While a snapshot path is configured, [`DegradedMode::spawn_snapshots`] keeps an
+/// on-disk [`Snapshot`] of the GET responses for all jobs, projects and skills. When the
+/// database becomes unreachable, [`serve_stale`] answers from it and
+/// [`DegradedMode::spawn_reconnect`] polls the database with exponential backoff
+/// until it is back.
+#[derive(Clone)]
+pub struct DegradedMode {
+    inner: Arc<Inner>,
+}
+
+impl DegradedMode {
+    /// Creates the mode with the database assumed reachable and no snapshot loaded.
+    /// Without a `path`, snapshots are neither loaded nor taken.
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                path,
+                snapshot: RwLock::new(None),
+                available: watch::Sender::new(true),
+            }),
+        }
+    }
+
+    /// Whether a snapshot path is configured
+    pub fn takes_snapshots(&self) -> bool {
+        self.inner.path.is_some()
+    }
+
+    /// Loads the snapshot from disk, if there is one, returning when it was taken.
+    pub fn load_snapshot(&self) -> Result<Option<DateTime<Utc>>, String> {
+        let Some(path) = self.inner.path.as_deref().filter(|path| path.exists()) else {
+            return Ok(None);
+        };
+
+        let snapshot = Snapshot::load(path)?;
+        let taken_at = snapshot.taken_at;
+        self.set_snapshot(snapshot);
+        Ok(Some(taken_at))
+    }
+
+    fn set_snapshot(&self, snapshot: Snapshot) {
+        *self
+            .inner
+            .snapshot
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(snapshot));
+    }
+
+    fn snapshot(&self) -> Option<Arc<Snapshot>> {
+        self.inner
+            .snapshot
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    pub fn has_snapshot(&self) -> bool {
+        self.snapshot().is_some()
+    }
+
+    pub fn is_available(&self) -> bool {
+        *self.inner.available.borrow()
+    }
+
+    /// Records that the database could not be reached, waking the reconnect task.
+    pub fn mark_unavailable(&self) {
+        if self.inner.available.send_replace(false) {
+            warn!("Database is unavailable; serving the snapshot where possible");
+        }
+    }
+
+    pub fn mark_available(&self) {
+        self.inner.available.send_replace(true);
+    }
+
+    /// Waits until the database is believed to be reachable.
+    pub async fn wait_until_available(&self) {
+        let mut available = self.inner.available.subscribe();
+        // The sender lives as long as `self`
+        let _ = available.wait_for(|available| *available).await;
+    }
+
+    /// The snapshot's response for `uri`, marked as stale.
+    pub fn stale_response(&self, uri: &str) -> Option<Response> {
+        let snapshot = self.snapshot()?;
+        let stored = snapshot.responses.get(uri)?;
+        let body = STANDARD.decode(&stored.body).ok()?;
+
+        let mut response = Response::new(Body::from(body));
+        let headers = response.headers_mut();
+        for (name, value) in &stored.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::try_from(name.as_str()),
+                HeaderValue::try_from(value.as_str()),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+        headers.insert(header::WARNING, HeaderValue::from_static(STALE_WARNING));
+        headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        let taken_at = snapshot.taken_at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        if let Ok(taken_at) = HeaderValue::from_str(&taken_at) {
+            headers.insert(X_DATA_STALE, taken_at);
+        }
+        Some(response)
+    }
+
+    /// Takes a new snapshot by requesting every URI of [`snapshot_uris`] from `router`,
+    /// following `Link: rel="next"` through paginated listings, and saves it. Returns the
+    /// number of responses stored.
+    ///
+    /// Fails without touching the current snapshot if any response is unavailable.
+    pub async fn capture(&self, router: &Router, state: &AppState) -> Result<usize, String> {
+        let Some(path) = self.inner.path.as_deref() else {
+            return Err("no snapshot path is configured".to_string());
+        };
+
+        let uris = snapshot_uris(&state.pool, &state.themes)
+            .await
+            .map_err(|e| format!("Failed to list snapshot URIs: {}", e))?;
+        let mut pending = VecDeque::from(uris);
+        let mut responses = BTreeMap::new();
+
+        while let Some(uri) = pending.pop_front() {
+            let request = Request::get(&uri)
+                .extension(Untracked)
+                .body(Body::empty())
+                .map_err(|e| format!("Invalid snapshot URI {}: {}", uri, e))?;
+            let response = router
+                .clone()
+                .oneshot(request)
+                .await
+                .unwrap_or_else(|never| match never {});
+
+            if response.headers().contains_key(X_DATA_STALE)
+                || response.status() == StatusCode::SERVICE_UNAVAILABLE
+            {
+                return Err(format!("{} is unavailable", uri));
+            }
+            // Resources deleted since they were listed
+            if response.status() != StatusCode::OK {
+                continue;
+            }
+
+            if let Some(next) = next_link(response.headers()) {
+                pending.push_back(next);
+            }
+            let (parts, body) = response.into_parts();
+            let body = axum::body::to_bytes(body, usize::MAX)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", uri, e))?;
+            let headers = KEPT_HEADERS
+                .iter()
+                .filter_map(|name| {
+                    let value = parts.headers.get(name)?.to_str().ok()?;
+                    Some((name.to_string(), value.to_string()))
+                })
+                .collect();
+            responses.insert(
+                uri,
+                StoredResponse {
+                    headers,
+                    body: STANDARD.encode(&body),
+                },
+            );
+        }
+
+        let count = responses.len();
+        let snapshot = Snapshot {
+            taken_at: Utc::now(),
+            responses,
+        };
+        snapshot.save(path)?;
+        self.set_snapshot(snapshot);
+        Ok(count)
+    }
+
+    /// Starts a task that takes a snapshot now and then every `interval`, skipping
+    /// rounds while the database is unavailable.
+    pub fn spawn_snapshots(
+        &self,
+        router: Router,
+        state: AppState,
+        interval: Duration,
+    ) -> JoinHandle<()> {
+        let degraded = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                degraded.wait_until_available().await;
+                match degraded.capture(&router, &state).await {
+                    Ok(count) => info!("Saved a snapshot of {} responses", count),
+                    Err(e) => warn!("Failed to take a snapshot: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Starts a task that, whenever the database is marked unavailable, polls it with
+    /// exponential backoff and marks it available again once it answers.
+    pub fn spawn_reconnect(&self, pool: PgPool) -> JoinHandle<()> {
+        let degraded = self.clone();
+        let mut available = self.inner.available.subscribe();
+        tokio::spawn(async move {
+            loop {
+                if available.wait_for(|available| !*available).await.is_err() {
+                    return;
+                }
+
+                let mut delay = MIN_RECONNECT_DELAY;
+                loop {
+                    match sqlx::query("SELECT 1").execute(&pool).await {
+                        Ok(_) => {
+                            info!("Database is reachable again");
+                            degraded.mark_available();
+                            break;
+                        }
+                        Err(e) => {
+                            warn!(
+                                "Database is still unavailable, retrying in {:?}: {}",
+                                delay, e
+                            );
+                            tokio::time::sleep(delay).await;
+                            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Target of a `Link: <uri>; rel="next"` header
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(header::LINK)?.to_str().ok()?;
+    if !link.contains("rel=\"next\"") {
+        return None;
+    }
+    let start = link.find('<')? + 1;
+    let end = link.find('>')?;
+    link.get(start..end).map(str::to_string)
+}
+
+/// The GET URIs a snapshot covers: every listing, every job, project and skill with
+/// their related resources, and every export, feed, SEO document and rendered page.
+pub async fn snapshot_uris(pool: &PgPool, themes: &Themes) -> Result<Vec<String>, sqlx::Error> {
+    let job_ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM jobs ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+    let project_ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM projects ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+    let skill_ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM skills ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+
+    let mut uris: Vec<String> = [
+        "/jobs",
+        "/projects",
+        "/skills",
+        "/skills/tree",
+        "/export/json-resume",
+        "/export/resume.pdf",
+        "/export/resume.md",
+        "/export/resume.txt",
+        "/feeds/projects.atom",
+        "/feeds/activity.rss",
+        "/seo/person.jsonld",
+        "/sitemap.xml",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .collect();
+
+    for id in job_ids {
+        uris.push(format!("/jobs/{}", id));
+        uris.push(format!("/projects/job/{}", id));
+    }
+    for id in project_ids {
+        uris.push(format!("/projects/{}", id));
+        uris.push(format!("/seo/projects/{}.jsonld", id));
+    }
+    for id in skill_ids {
+        uris.push(format!("/skills/{}", id));
+        uris.push(format!("/skills/{}/ancestors", id));
+        uris.push(format!("/skills/{}/descendants", id));
+        uris.push(format!("/projects/skill/{}", id));
+    }
+    uris.extend(themes.names().map(|theme| format!("/render/{}", theme)));
+
+    Ok(uris)
+}
+
+/// Middleware that answers GET and HEAD requests from the snapshot while the database
+/// is unavailable, with `Warning` and `X-Data-Stale` headers.
+///
+/// A `503` from a handler marks the database unavailable. Requests the snapshot does
+/// not cover still reach their handler.
+pub async fn serve_stale(
+    State(degraded): State<DegradedMode>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+
+    let uri = request.uri().to_string();
+    if !degraded.is_available()
+        && let Some(stale) = degraded.stale_response(&uri)
+    {
+        return stale;
+    }
+
+    let response = next.run(request).await;
+    if response.status() == StatusCode::SERVICE_UNAVAILABLE {
+        degraded.mark_unavailable();
+        if let Some(stale) = degraded.stale_response(&uri) {
+            return stale;
+        }
+    }
+    response
+}
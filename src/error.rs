@@ -0,0 +1,246 @@
+use crate::db::jobs_db::JobWriteError;
+use crate::db::skills_db::SkillWriteError;
+use crate::metrics::METRICS;
+use crate::request_id::current_request_id;
+use axum::Json;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use utoipa::ToSchema;
+
+/// Media type of every error body, as defined by RFC 7807
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// RFC 7807 problem details returned for every error response
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct ProblemDetails {
+    /// Stable identifier of the problem kind; clients should branch on this
+    #[serde(rename = "type")]
+    #[schema(example = "/problems/not-found")]
+    pub problem_type: String,
+    /// Short, human readable summary of the problem kind
+    #[schema(example = "Resource not found")]
+    pub title: String,
+    /// HTTP status code of the response
+    #[schema(example = 404)]
+    pub status: u16,
+    /// Explanation specific to this occurrence of the problem
+    #[schema(example = "Job not found")]
+    pub detail: String,
+    /// Id of the request, also sent in the `X-Request-Id` header
+    #[schema(example = "3f2c1e9a7b6d4c8e9f0a1b2c3d4e5f60")]
+    pub request_id: Option<String>,
+}
+
+/// Error type shared by every handler and middleware
+#[derive(Debug)]
+pub enum ApiError {
+    /// The requested resource does not exist
+    NotFound(String),
+    /// The request was well-formed but its content is invalid
+    Validation(String),
+    /// The request could not be routed or parsed (unknown method, bad JSON, path or query)
+    InvalidRequest { status: StatusCode, detail: String },
+    /// The request conflicts with the current state of the resource
+    Conflict(String),
+    /// No valid credentials were supplied
+    Unauthorized(String),
+    /// The credentials do not allow the operation
+    Forbidden(String),
+    /// The database could not be reached
+    ServiceUnavailable(String),
+    /// Any other database failure
+    Database(sqlx::Error),
+    /// Any other unexpected failure
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::InvalidRequest { status, .. } => *status,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Database(_) | ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// The stable `type` and `title` of the problem
+    fn kind(&self) -> (&'static str, &'static str) {
+        match self {
+            ApiError::NotFound(_) => ("/problems/not-found", "Resource not found"),
+            ApiError::Validation(_) => ("/problems/validation", "Validation failed"),
+            ApiError::InvalidRequest { .. } => ("/problems/invalid-request", "Invalid request"),
+            ApiError::Conflict(_) => ("/problems/conflict", "Conflict"),
+            ApiError::Unauthorized(_) => ("/problems/unauthorized", "Authentication required"),
+            ApiError::Forbidden(_) => ("/problems/forbidden", "Insufficient scope"),
+            ApiError::ServiceUnavailable(_) => {
+                ("/problems/service-unavailable", "Service unavailable")
+            }
+            ApiError::Database(_) | ApiError::Internal(_) => {
+                ("/problems/internal", "Internal server error")
+            }
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            ApiError::NotFound(detail)
+            | ApiError::Validation(detail)
+            | ApiError::InvalidRequest { detail, .. }
+            | ApiError::Conflict(detail)
+            | ApiError::Unauthorized(detail)
+            | ApiError::Forbidden(detail)
+            | ApiError::ServiceUnavailable(detail) => detail.clone(),
+            // Internal details stay in the logs
+            ApiError::Database(_) | ApiError::Internal(_) => {
+                "An unexpected error occurred".to_string()
+            }
+        }
+    }
+
+    /// Builds the problem details body for this error
+    pub fn to_problem(&self) -> ProblemDetails {
+        let (problem_type, title) = self.kind();
+        ProblemDetails {
+            problem_type: problem_type.to_string(),
+            title: title.to_string(),
+            status: self.status().as_u16(),
+            detail: self.detail(),
+            request_id: current_request_id(),
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::Database(e) => write!(f, "database error: {}", e),
+            ApiError::Internal(detail) => write!(f, "internal error: {}", detail),
+            other => write!(f, "{}", other.detail()),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(e: sqlx::Error) -> Self {
+        match &e {
+            sqlx::Error::RowNotFound => ApiError::NotFound("Resource not found".to_string()),
+            sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+                ApiError::Validation("Referenced resource does not exist".to_string())
+            }
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                ApiError::Conflict("Resource already exists".to_string())
+            }
+            sqlx::Error::Database(db_error) if db_error.is_check_violation() => {
+                ApiError::Validation(db_error.message().to_string())
+            }
+            sqlx::Error::ColumnDecode { index, source } => {
+                error!("Failed to decode column {}: {}", index, source);
+                ApiError::Database(e)
+            }
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => {
+                error!("Database unavailable: {:?}", e);
+                if matches!(e, sqlx::Error::PoolTimedOut) {
+                    METRICS.record_pool_timeout();
+                }
+                ApiError::ServiceUnavailable("Database is unavailable".to_string())
+            }
+            _ => ApiError::Database(e),
+        }
+    }
+}
+
+impl From<minijinja::Error> for ApiError {
+    fn from(e: minijinja::Error) -> Self {
+        ApiError::Internal(format!("failed to render template: {:#}", e))
+    }
+}
+
+impl From<JobWriteError> for ApiError {
+    fn from(e: JobWriteError) -> Self {
+        match e {
+            JobWriteError::Invalid(detail) => ApiError::Validation(detail),
+            JobWriteError::Database(e) => e.into(),
+        }
+    }
+}
+
+impl From<SkillWriteError> for ApiError {
+    fn from(e: SkillWriteError) -> Self {
+        match e {
+            SkillWriteError::Invalid(detail) => ApiError::Validation(detail),
+            SkillWriteError::ParentNotFound(parent_id) => {
+                ApiError::Validation(format!("Parent skill {} does not exist", parent_id))
+            }
+            SkillWriteError::ParentCycle(parent_id) => ApiError::Validation(format!(
+                "Skill {} is this skill or one of its descendants and cannot be its parent",
+                parent_id
+            )),
+            SkillWriteError::InUse { projects, children } => ApiError::Conflict(format!(
+                "Skill is used by {} project(s) and has {} child skill(s); retry with ?cascade=true to delete them too",
+                projects, children
+            )),
+            SkillWriteError::Database(e) => e.into(),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection.status() {
+            StatusCode::UNPROCESSABLE_ENTITY => ApiError::Validation(rejection.body_text()),
+            status => ApiError::InvalidRequest {
+                status,
+                detail: rejection.body_text(),
+            },
+        }
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        ApiError::InvalidRequest {
+            status: rejection.status(),
+            detail: rejection.body_text(),
+        }
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::InvalidRequest {
+            status: rejection.status(),
+            detail: rejection.body_text(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let problem = self.to_problem();
+
+        if self.status().is_server_error() {
+            error!(request_id = ?problem.request_id, "{}", self);
+        }
+
+        let mut response = (self.status(), Json(problem)).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        if let ApiError::Unauthorized(_) = self {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
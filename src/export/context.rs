@@ -0,0 +1,126 @@
+use crate::export::json_resume::{level, lines};
+use crate::export::{ResumeData, ResumeSection, date_range};
+use crate::models::profile::Profile;
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// The values résumé templates are rendered with
+#[derive(Serialize, Debug)]
+pub struct ResumeContext<'a> {
+    pub profile: &'a Profile,
+    /// Names of the sections to render, in order: `work`, `skills` or `projects`
+    pub sections: Vec<&'static str>,
+    /// Most recent first
+    pub jobs: Vec<JobView<'a>>,
+    /// Top-level skills by name
+    pub skills: Vec<SkillGroupView>,
+    pub projects: Vec<ProjectView<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct JobView<'a> {
+    pub id: i32,
+    pub company_name: &'a str,
+    /// Empty when the job has no website
+    pub company_website: &'a str,
+    pub roles: &'a str,
+    /// Formatted dates, e.g. "Jan 2020 – Present"
+    pub dates: String,
+    pub start_date: NaiveDate,
+    /// None for the current job
+    pub end_date: Option<NaiveDate>,
+    pub is_current_job: bool,
+    pub description: &'a str,
+    /// One entry per line of the job's responsibilities
+    pub responsibilities: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SkillGroupView {
+    pub id: i32,
+    pub name: String,
+    /// Proficiency, e.g. "Advanced"
+    pub level: &'static str,
+    /// The skills below this one in the hierarchy, most proficient first
+    pub levels: Vec<LevelView>,
+}
+
+/// Skills sharing one proficiency
+#[derive(Serialize, Debug)]
+pub struct LevelView {
+    pub level: &'static str,
+    pub skills: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ProjectView<'a> {
+    pub id: i32,
+    pub name: &'a str,
+    pub description: &'a str,
+    pub github_url: Option<&'a str>,
+    /// Names of the skills the project uses
+    pub skills: Vec<&'a str>,
+}
+
+impl<'a> ResumeContext<'a> {
+    pub fn new(profile: &'a Profile, data: &'a ResumeData, sections: &[ResumeSection]) -> Self {
+        let jobs = data
+            .jobs
+            .iter()
+            .map(|job| JobView {
+                id: job.id,
+                company_name: &job.company_name,
+                company_website: job.company_website.trim(),
+                roles: job.roles.trim(),
+                dates: date_range(job),
+                start_date: job.start_date,
+                end_date: job.end_date.filter(|_| !job.is_current_job),
+                is_current_job: job.is_current_job,
+                description: job.description.trim(),
+                responsibilities: lines(&job.responsibilities),
+            })
+            .collect();
+
+        let skills = data
+            .skill_groups()
+            .iter()
+            .map(|group| SkillGroupView {
+                id: group.skill.id,
+                name: group.skill.name.clone(),
+                level: level(&group.skill.proficiency),
+                levels: group
+                    .by_proficiency()
+                    .into_iter()
+                    .map(|(proficiency, skills)| LevelView {
+                        level: level(&proficiency),
+                        skills: skills.iter().map(|skill| skill.name.clone()).collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let projects = data
+            .projects
+            .iter()
+            .map(|project| ProjectView {
+                id: project.id,
+                name: &project.name,
+                description: project.description.trim(),
+                github_url: project.github_url.as_deref(),
+                skills: project
+                    .skills
+                    .iter()
+                    .map(|skill| skill.name.as_str())
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            profile,
+            sections: sections.iter().map(|section| section.name()).collect(),
+            jobs,
+            skills,
+            projects,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Metrics and encoding for the standard Helvetica fonts, which every PDF reader
+//! provides, so résumés need no embedded font files.
+
+/// Advance widths of the printable ASCII characters (32-126) in Helvetica, in
+/// thousandths of the font size, from the Adobe font metrics
+const REGULAR_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' ' - '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584,
+    556, // '0' - '?'
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722,
+    778, // '@' - 'O'
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469,
+    556, // 'P' - '_'
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556,
+    556, // '`' - 'o'
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // 'p' - '~'
+];
+
+/// The same for Helvetica-Bold
+const BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' ' - '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584,
+    611, // '0' - '?'
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722,
+    778, // '@' - 'O'
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584,
+    556, // 'P' - '_'
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611,
+    611, // '`' - 'o'
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584, // 'p' - '~'
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    /// PostScript name of the standard font
+    pub fn base_font(self) -> &'static [u8] {
+        match self {
+            Font::Regular => b"Helvetica",
+            Font::Bold => b"Helvetica-Bold",
+        }
+    }
+
+    /// Width of `text` set at `size` points
+    pub fn measure(self, text: &str, size: f32) -> f32 {
+        let widths = match self {
+            Font::Regular => &REGULAR_WIDTHS,
+            Font::Bold => &BOLD_WIDTHS,
+        };
+        let units: u32 = encode(text)
+            .into_iter()
+            .map(|byte| match byte {
+                32..=126 => u32::from(widths[usize::from(byte - 32)]),
+                0x95 => 350,
+                0x97 => 1000,
+                0x91..=0x94 => 333,
+                _ => 556,
+            })
+            .sum();
+        units as f32 * size / 1000.0
+    }
+}
+
+/// Encodes `text` in WinAnsiEncoding, replacing characters it lacks with `?`
+pub fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '\t' | '\n' | '\r' => b' ',
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201c}' => 0x93,
+            '\u{201d}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{2026}' => 0x85,
+            '\u{20ac}' => 0x80,
+            _ => b'?',
+        })
+        .collect()
+}
@@ -0,0 +1,146 @@
+use crate::db::proficiency_enum::Proficiency;
+use crate::export::ResumeData;
+use crate::models::profile::Profile;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Version of the jsonresume.org schema the export follows
+pub const JSON_RESUME_SCHEMA: &str =
+    "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+/// A résumé in the jsonresume.org format
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct JsonResume {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub basics: Profile,
+    pub work: Vec<Work>,
+    pub skills: Vec<ResumeSkill>,
+    pub projects: Vec<ResumeProject>,
+}
+
+/// A job in the JSON Resume `work` section
+#[derive(Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+    /// Company name
+    pub name: String,
+    pub position: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[schema(example = "2023-01-01")]
+    pub start_date: String,
+    /// Omitted for the current job
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "2024-01-01")]
+    pub end_date: Option<String>,
+    pub summary: String,
+    /// One entry per line of the job's responsibilities
+    pub highlights: Vec<String>,
+}
+
+/// A top-level skill in the JSON Resume `skills` section
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ResumeSkill {
+    pub name: String,
+    /// The skill's proficiency, e.g. "Advanced"
+    pub level: String,
+    /// Names of the skills below it in the hierarchy
+    pub keywords: Vec<String>,
+}
+
+/// A project in the JSON Resume `projects` section
+#[derive(Serialize, Debug, Clone, ToSchema)]
+pub struct ResumeProject {
+    pub name: String,
+    pub description: String,
+    /// The project's GitHub URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Names of the skills used by the project
+    pub keywords: Vec<String>,
+}
+
+/// The JSON Resume `level` for a proficiency
+pub fn level(proficiency: &Proficiency) -> &'static str {
+    match proficiency {
+        Proficiency::Beginner => "Beginner",
+        Proficiency::Intermediate => "Intermediate",
+        Proficiency::Advanced => "Advanced",
+        Proficiency::Expert => "Expert",
+    }
+}
+
+/// Splits free text into its non-blank lines, trimmed
+pub fn lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Trimmed text, or None when it is blank
+pub fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+impl JsonResume {
+    pub fn new(profile: &Profile, data: &ResumeData) -> Self {
+        let work = data
+            .jobs
+            .iter()
+            .map(|job| Work {
+                name: job.company_name.clone(),
+                position: job.roles.clone(),
+                url: non_empty(&job.company_website),
+                start_date: job.start_date.to_string(),
+                end_date: if job.is_current_job {
+                    None
+                } else {
+                    job.end_date.map(|date| date.to_string())
+                },
+                summary: job.description.clone(),
+                highlights: lines(&job.responsibilities),
+            })
+            .collect();
+
+        let skills = data
+            .skill_groups()
+            .into_iter()
+            .map(|group| ResumeSkill {
+                name: group.skill.name,
+                level: level(&group.skill.proficiency).to_string(),
+                keywords: group
+                    .sub_skills
+                    .into_iter()
+                    .map(|skill| skill.name)
+                    .collect(),
+            })
+            .collect();
+
+        let projects = data
+            .projects
+            .iter()
+            .map(|project| ResumeProject {
+                name: project.name.clone(),
+                description: project.description.clone(),
+                url: project.github_url.as_deref().and_then(non_empty),
+                keywords: project
+                    .skills
+                    .iter()
+                    .map(|skill| skill.name.clone())
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            schema: JSON_RESUME_SCHEMA.to_string(),
+            basics: profile.clone(),
+            work,
+            skills,
+            projects,
+        }
+    }
+}
@@ -0,0 +1,216 @@
+pub mod context;
+mod helvetica;
+pub mod json_resume;
+pub mod pdf;
+pub mod text;
+
+use crate::db::proficiency_enum::Proficiency;
+use crate::db::{jobs_db, projects_db, skills_db};
+use crate::models::job::Job;
+use crate::models::project::Project;
+use crate::models::skill::Skill;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::mem::discriminant;
+use utoipa::IntoParams;
+
+/// A section of a rendered résumé
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeSection {
+    Work,
+    Skills,
+    Projects,
+}
+
+impl ResumeSection {
+    const ALL: [ResumeSection; 3] = [
+        ResumeSection::Work,
+        ResumeSection::Skills,
+        ResumeSection::Projects,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            ResumeSection::Work => "work",
+            ResumeSection::Skills => "skills",
+            ResumeSection::Projects => "projects",
+        }
+    }
+}
+
+/// Query parameters choosing what a rendered résumé contains
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ResumeParams {
+    /// Comma-separated sections to include, in the order given: `work`, `skills`
+    /// and `projects` (default all three)
+    pub sections: Option<String>,
+    /// Comma-separated IDs of the projects to include (default all)
+    pub projects: Option<String>,
+}
+
+impl ResumeParams {
+    /// The requested sections, in order
+    pub fn sections(&self) -> Result<Vec<ResumeSection>, String> {
+        let Some(sections) = &self.sections else {
+            return Ok(ResumeSection::ALL.to_vec());
+        };
+
+        let mut parsed = Vec::new();
+        for name in sections.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let section = ResumeSection::ALL
+                .into_iter()
+                .find(|section| section.name() == name)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown section `{}`; expected work, skills or projects",
+                        name
+                    )
+                })?;
+            if !parsed.contains(&section) {
+                parsed.push(section);
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// The requested project IDs, or None for every project
+    pub fn project_ids(&self) -> Result<Option<Vec<i32>>, String> {
+        self.projects
+            .as_deref()
+            .map(|ids| {
+                ids.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| {
+                        id.parse()
+                            .map_err(|_| format!("`{}` is not a valid project ID", id))
+                    })
+                    .collect()
+            })
+            .transpose()
+    }
+}
+
+/// Formats a job's dates as e.g. "Jan 2020 – Mar 2023" or "Jan 2020 – Present"
+pub fn date_range(job: &Job) -> String {
+    let end = match job.end_date {
+        _ if job.is_current_job => "Present".to_string(),
+        Some(end_date) => month_year(end_date),
+        None => "Present".to_string(),
+    };
+    format!("{} \u{2013} {}", month_year(job.start_date), end)
+}
+
+fn month_year(date: NaiveDate) -> String {
+    date.format("%b %Y").to_string()
+}
+
+/// Everything an exported résumé is built from: the same rows as `/jobs`,
+/// `/projects` and `/skills`, unpaginated
+#[derive(Debug, Clone)]
+pub struct ResumeData {
+    /// Most recent first
+    pub jobs: Vec<Job>,
+    /// Ordered by name
+    pub skills: Vec<Skill>,
+    pub projects: Vec<Project>,
+}
+
+/// A top-level skill and every skill below it in the hierarchy
+#[derive(Debug, Clone)]
+pub struct SkillGroup {
+    pub skill: Skill,
+    /// Descendants in depth-first order, siblings by name
+    pub sub_skills: Vec<Skill>,
+}
+
+impl SkillGroup {
+    /// The sub-skills grouped by proficiency, most proficient first, leaving out
+    /// levels with no skills
+    pub fn by_proficiency(&self) -> Vec<(Proficiency, Vec<&Skill>)> {
+        [
+            Proficiency::Expert,
+            Proficiency::Advanced,
+            Proficiency::Intermediate,
+            Proficiency::Beginner,
+        ]
+        .into_iter()
+        .map(|proficiency| {
+            let skills: Vec<&Skill> = self
+                .sub_skills
+                .iter()
+                .filter(|skill| discriminant(&skill.proficiency) == discriminant(&proficiency))
+                .collect();
+            (proficiency, skills)
+        })
+        .filter(|(_, skills)| !skills.is_empty())
+        .collect()
+    }
+}
+
+impl ResumeData {
+    pub async fn fetch(pool: &PgPool) -> Result<Self, sqlx::Error> {
+        let (jobs, skills, projects) = tokio::try_join!(
+            jobs_db::fetch_all_jobs(pool),
+            skills_db::fetch_all_skills(pool),
+            projects_db::fetch_all_projects(pool),
+        )?;
+
+        Ok(Self {
+            jobs,
+            skills,
+            projects,
+        })
+    }
+
+    /// Keeps only the projects with the given IDs, in their existing order
+    pub fn retain_projects(&mut self, project_ids: &[i32]) {
+        self.projects
+            .retain(|project| project_ids.contains(&project.id));
+    }
+
+    /// Groups the skills under their top-level ancestors, ordered by name.
+    ///
+    /// Skills caught in a `parent_id` cycle have no top-level ancestor and are left out.
+    pub fn skill_groups(&self) -> Vec<SkillGroup> {
+        let mut children: HashMap<i32, Vec<&Skill>> = HashMap::new();
+        for skill in &self.skills {
+            if let Some(parent_id) = skill.parent_id {
+                children.entry(parent_id).or_default().push(skill);
+            }
+        }
+
+        self.skills
+            .iter()
+            .filter(|skill| skill.parent_id.is_none())
+            .map(|root| {
+                let mut sub_skills = Vec::new();
+                let mut visited = HashSet::from([root.id]);
+                let mut stack: Vec<&Skill> = children_of(&children, root.id);
+                while let Some(skill) = stack.pop() {
+                    if !visited.insert(skill.id) {
+                        continue;
+                    }
+                    sub_skills.push(skill.clone());
+                    stack.extend(children_of(&children, skill.id));
+                }
+
+                SkillGroup {
+                    skill: root.clone(),
+                    sub_skills,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Children of `skill_id` reversed, so popping them off a stack visits them by name
+fn children_of<'a>(children: &HashMap<i32, Vec<&'a Skill>>, skill_id: i32) -> Vec<&'a Skill> {
+    children
+        .get(&skill_id)
+        .map(|skills| skills.iter().rev().copied().collect())
+        .unwrap_or_default()
+}
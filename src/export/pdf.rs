@@ -0,0 +1,373 @@
+use crate::export::helvetica::{self, Font};
+use crate::export::json_resume::{level, lines};
+use crate::export::{ResumeData, ResumeSection, date_range};
+use crate::models::profile::Profile;
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str, TextStr};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+const MARGIN: f32 = 54.0;
+const LINE_SPACING: f32 = 1.3;
+const BODY_SIZE: f32 = 10.0;
+const ACCENT: (f32, f32, f32) = (0.16, 0.29, 0.45);
+
+/// Paper size of a PDF résumé
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PageSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    /// Width and height in points
+    fn dimensions(self) -> (f32, f32) {
+        match self {
+            PageSize::A4 => (595.28, 841.89),
+            PageSize::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+/// Query parameters specific to the PDF résumé
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PdfParams {
+    /// Paper size, `a4` (default) or `letter`
+    #[serde(default)]
+    #[param(inline)]
+    pub page_size: PageSize,
+}
+
+/// Renders the résumé as a PDF using the standard Helvetica fonts.
+///
+/// Text is wrapped to the page width and flows onto new pages as needed; every page
+/// gets a footer with the page number.
+pub fn render_pdf(
+    profile: &Profile,
+    data: &ResumeData,
+    sections: &[ResumeSection],
+    page_size: PageSize,
+) -> Vec<u8> {
+    let mut layout = Layout::new(page_size);
+
+    if !profile.name.is_empty() {
+        layout.line(&profile.name, Font::Bold, 22.0, 0.0);
+    }
+    let contact: Vec<&str> = [&profile.label, &profile.email, &profile.phone, &profile.url]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    if !contact.is_empty() {
+        layout.paragraph(&contact.join("  \u{2022}  "), Font::Regular, BODY_SIZE, 0.0);
+    }
+    if let Some(summary) = &profile.summary {
+        layout.gap(4.0);
+        layout.paragraph(summary, Font::Regular, BODY_SIZE, 0.0);
+    }
+
+    for section in sections {
+        match section {
+            ResumeSection::Work => work_section(&mut layout, data),
+            ResumeSection::Skills => skills_section(&mut layout, data),
+            ResumeSection::Projects => projects_section(&mut layout, data),
+        }
+    }
+
+    layout.finish(&profile.name)
+}
+
+fn work_section(layout: &mut Layout, data: &ResumeData) {
+    if data.jobs.is_empty() {
+        return;
+    }
+    layout.heading("Experience");
+
+    for job in &data.jobs {
+        let title = if job.roles.trim().is_empty() {
+            job.company_name.clone()
+        } else {
+            format!("{} \u{2013} {}", job.roles.trim(), job.company_name)
+        };
+        layout.keep_together(3.0 * BODY_SIZE * LINE_SPACING);
+        layout.split_line(&title, &date_range(job));
+        if !job.description.trim().is_empty() {
+            layout.paragraph(&job.description, Font::Regular, BODY_SIZE, 0.0);
+        }
+        for responsibility in lines(&job.responsibilities) {
+            layout.bullet(&responsibility);
+        }
+        layout.gap(6.0);
+    }
+}
+
+fn skills_section(layout: &mut Layout, data: &ResumeData) {
+    let groups = data.skill_groups();
+    if groups.is_empty() {
+        return;
+    }
+    layout.heading("Skills");
+
+    for group in groups {
+        layout.keep_together(2.0 * BODY_SIZE * LINE_SPACING);
+        layout.split_line(&group.skill.name, level(&group.skill.proficiency));
+        for (proficiency, skills) in group.by_proficiency() {
+            let names: Vec<&str> = skills.iter().map(|skill| skill.name.as_str()).collect();
+            let text = format!("{}: {}", level(&proficiency), names.join(", "));
+            layout.paragraph(&text, Font::Regular, BODY_SIZE, 12.0);
+        }
+        layout.gap(4.0);
+    }
+}
+
+fn projects_section(layout: &mut Layout, data: &ResumeData) {
+    if data.projects.is_empty() {
+        return;
+    }
+    layout.heading("Projects");
+
+    for project in &data.projects {
+        layout.keep_together(3.0 * BODY_SIZE * LINE_SPACING);
+        layout.split_line(&project.name, project.github_url.as_deref().unwrap_or(""));
+        if !project.description.trim().is_empty() {
+            layout.paragraph(&project.description, Font::Regular, BODY_SIZE, 0.0);
+        }
+        if !project.skills.is_empty() {
+            let skills: Vec<&str> = project.skills.iter().map(|s| s.name.as_str()).collect();
+            let text = format!("Skills: {}", skills.join(", "));
+            layout.paragraph(&text, Font::Regular, BODY_SIZE - 1.0, 0.0);
+        }
+        layout.gap(6.0);
+    }
+}
+
+/// Flows lines of text down pages, starting a new page when one is full
+struct Layout {
+    width: f32,
+    height: f32,
+    pages: Vec<Content>,
+    /// Baseline of the next line on the current page
+    y: f32,
+}
+
+impl Layout {
+    fn new(page_size: PageSize) -> Self {
+        let (width, height) = page_size.dimensions();
+        let mut layout = Self {
+            width,
+            height,
+            pages: Vec::new(),
+            y: 0.0,
+        };
+        layout.new_page();
+        layout
+    }
+
+    fn text_width(&self) -> f32 {
+        self.width - 2.0 * MARGIN
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(Content::new());
+        self.y = self.height - MARGIN;
+    }
+
+    /// Starts a new page unless `height` points still fit above the footer
+    fn keep_together(&mut self, height: f32) {
+        if self.y - height < MARGIN + BODY_SIZE * 2.0 {
+            self.new_page();
+        }
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn show(&mut self, x: f32, text: &str, font: Font, size: f32) {
+        let content = self.pages.last_mut().expect("layout always has a page");
+        content
+            .begin_text()
+            .set_font(font_name(font), size)
+            .next_line(x, self.y)
+            .show(Str(&helvetica::encode(text)))
+            .end_text();
+    }
+
+    /// Writes a single unwrapped line
+    fn line(&mut self, text: &str, font: Font, size: f32, indent: f32) {
+        self.keep_together(size * LINE_SPACING);
+        self.y -= size;
+        self.show(MARGIN + indent, text, font, size);
+        self.y -= size * (LINE_SPACING - 1.0);
+    }
+
+    /// Writes text wrapped to the page width
+    fn paragraph(&mut self, text: &str, font: Font, size: f32, indent: f32) {
+        for line in wrap(text, font, size, self.text_width() - indent) {
+            self.line(&line, font, size, indent);
+        }
+    }
+
+    fn bullet(&mut self, text: &str) {
+        let indent = 12.0;
+        let lines = wrap(text, Font::Regular, BODY_SIZE, self.text_width() - indent);
+        for (i, line) in lines.iter().enumerate() {
+            self.keep_together(BODY_SIZE * LINE_SPACING);
+            if i == 0 {
+                let y = self.y;
+                self.y -= BODY_SIZE;
+                self.show(MARGIN + 2.0, "\u{2022}", Font::Regular, BODY_SIZE);
+                self.y = y;
+            }
+            self.line(line, Font::Regular, BODY_SIZE, indent);
+        }
+    }
+
+    /// Writes a bold title on the left and regular text flush right on the same line
+    fn split_line(&mut self, left: &str, right: &str) {
+        let right_width = Font::Regular.measure(right, BODY_SIZE);
+        let left_width = self.text_width() - right_width - 12.0;
+        let mut lines = wrap(left, Font::Bold, BODY_SIZE + 1.0, left_width).into_iter();
+
+        let first = lines.next().unwrap_or_default();
+        self.keep_together((BODY_SIZE + 1.0) * LINE_SPACING);
+        let y = self.y;
+        self.y -= BODY_SIZE + 1.0;
+        self.show(
+            self.width - MARGIN - right_width,
+            right,
+            Font::Regular,
+            BODY_SIZE,
+        );
+        self.y = y;
+        self.line(&first, Font::Bold, BODY_SIZE + 1.0, 0.0);
+        for line in lines {
+            self.line(&line, Font::Bold, BODY_SIZE + 1.0, 0.0);
+        }
+    }
+
+    fn heading(&mut self, title: &str) {
+        self.keep_together(48.0);
+        self.gap(10.0);
+        let content = self.pages.last_mut().expect("layout always has a page");
+        content.set_fill_rgb(ACCENT.0, ACCENT.1, ACCENT.2);
+        self.line(&title.to_uppercase(), Font::Bold, 12.0, 0.0);
+
+        let (r, g, b) = ACCENT;
+        let y = self.y + 3.0;
+        let right = self.width - MARGIN;
+        let content = self.pages.last_mut().expect("layout always has a page");
+        content
+            .set_stroke_rgb(r, g, b)
+            .set_line_width(0.75)
+            .move_to(MARGIN, y)
+            .line_to(right, y)
+            .stroke()
+            .set_fill_rgb(0.0, 0.0, 0.0);
+        self.gap(6.0);
+    }
+
+    /// Adds the footers and assembles the pages into a PDF document
+    fn finish(mut self, title: &str) -> Vec<u8> {
+        let page_count = self.pages.len();
+        for (i, content) in self.pages.iter_mut().enumerate() {
+            let footer = format!("Page {} of {}", i + 1, page_count);
+            let x = (self.width - Font::Regular.measure(&footer, 8.0)) / 2.0;
+            content
+                .set_fill_rgb(0.4, 0.4, 0.4)
+                .begin_text()
+                .set_font(font_name(Font::Regular), 8.0)
+                .next_line(x, MARGIN / 2.0)
+                .show(Str(&helvetica::encode(&footer)))
+                .end_text();
+        }
+
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let regular_id = Ref::new(3);
+        let bold_id = Ref::new(4);
+        let info_id = Ref::new(5);
+        let page_ids: Vec<(Ref, Ref)> = (0..page_count as i32)
+            .map(|i| (Ref::new(6 + 2 * i), Ref::new(7 + 2 * i)))
+            .collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().map(|(page_id, _)| *page_id))
+            .count(page_count as i32);
+        for (font_id, font) in [(regular_id, Font::Regular), (bold_id, Font::Bold)] {
+            pdf.type1_font(font_id)
+                .base_font(Name(font.base_font()))
+                .encoding_predefined(Name(b"WinAnsiEncoding"));
+        }
+        {
+            let mut info = pdf.document_info(info_id);
+            if !title.is_empty() {
+                info.title(TextStr(&format!("{} \u{2013} R\u{e9}sum\u{e9}", title)));
+                info.author(TextStr(title));
+            }
+            info.producer(TextStr("portfolio-api"));
+        }
+
+        for ((page_id, content_id), content) in page_ids.into_iter().zip(self.pages) {
+            {
+                let mut page = pdf.page(page_id);
+                page.media_box(Rect::new(0.0, 0.0, self.width, self.height))
+                    .parent(page_tree_id)
+                    .contents(content_id);
+                page.resources()
+                    .fonts()
+                    .pair(font_name(Font::Regular), regular_id)
+                    .pair(font_name(Font::Bold), bold_id);
+            }
+            pdf.stream(content_id, &content.finish());
+        }
+
+        pdf.finish()
+    }
+}
+
+fn font_name(font: Font) -> Name<'static> {
+    match font {
+        Font::Regular => Name(b"F1"),
+        Font::Bold => Name(b"F2"),
+    }
+}
+
+/// Greedily wraps `text` into lines no wider than `max_width`, breaking words that
+/// are wider than a whole line
+fn wrap(text: &str, font: Font, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if font.measure(&candidate, size) <= max_width {
+            current = candidate;
+            continue;
+        }
+
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        for c in word.chars() {
+            current.push(c);
+            if font.measure(&current, size) > max_width && current.chars().count() > 1 {
+                current.pop();
+                lines.push(std::mem::replace(&mut current, c.to_string()));
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
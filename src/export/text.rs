@@ -0,0 +1,39 @@
+use crate::export::context::ResumeContext;
+use minijinja::Environment;
+use std::sync::LazyLock;
+
+const MARKDOWN_TEMPLATE: &str = "resume.md";
+const TEXT_TEMPLATE: &str = "resume.txt";
+
+/// Templates for the text résumés, compiled on first use
+static TEMPLATES: LazyLock<Environment<'static>> = LazyLock::new(|| {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.add_filter("underline", underline);
+    env.add_template(MARKDOWN_TEMPLATE, include_str!("templates/resume.md.j2"))
+        .expect("Markdown résumé template is valid");
+    env.add_template(TEXT_TEMPLATE, include_str!("templates/resume.txt.j2"))
+        .expect("plain-text résumé template is valid");
+    env
+});
+
+/// Renders the résumé as Markdown, linking projects to their GitHub repositories
+pub fn render_markdown(context: &ResumeContext) -> Result<String, minijinja::Error> {
+    render(MARKDOWN_TEMPLATE, context)
+}
+
+/// Renders the résumé as plain text with underlined section headings
+pub fn render_plain_text(context: &ResumeContext) -> Result<String, minijinja::Error> {
+    render(TEXT_TEMPLATE, context)
+}
+
+fn render(name: &str, context: &ResumeContext) -> Result<String, minijinja::Error> {
+    let rendered = TEMPLATES.get_template(name)?.render(context)?;
+    Ok(format!("{}\n", rendered.trim()))
+}
+
+/// A line of `=` as long as `text`, to set under a heading
+fn underline(text: &str) -> String {
+    "=".repeat(text.chars().count())
+}
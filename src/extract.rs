@@ -0,0 +1,17 @@
+use crate::error::ApiError;
+use axum::extract::{FromRequest, FromRequestParts};
+
+/// `axum::Json` extractor whose rejections are problem+json [`ApiError`]s
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct ApiJson<T>(pub T);
+
+/// `axum::extract::Path` extractor whose rejections are problem+json [`ApiError`]s
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiError))]
+pub struct ApiPath<T>(pub T);
+
+/// `axum::extract::Query` extractor whose rejections are problem+json [`ApiError`]s
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
@@ -0,0 +1,237 @@
+//! Atom and RSS feeds of portfolio changes.
+
+use crate::export::json_resume::non_empty;
+use crate::models::feed::{JobActivity, ProjectActivity};
+use crate::models::profile::Profile;
+use crate::models::project::Project;
+use crate::seo::SiteConfig;
+use atom_syndication::{Category, Entry, Feed, Generator, Link, Person, Text};
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Renders the projects as an Atom feed, one entry per project.
+///
+/// Projects are expected most recently changed first. Entries link to the project's
+/// GitHub repository, or its page on the site without one, and carry its skill names
+/// as categories.
+pub fn projects_atom(profile: &Profile, site: &SiteConfig, projects: &[ProjectActivity]) -> String {
+    let entries = projects
+        .iter()
+        .map(|activity| {
+            let project = &activity.project;
+            Entry {
+                title: Text::plain(project.name.clone()),
+                id: project_urn(project),
+                updated: activity.updated_at.fixed_offset(),
+                published: Some(activity.created_at.fixed_offset()),
+                summary: non_empty(&project.description).map(Text::plain),
+                links: vec![Link {
+                    href: project_link(project, site),
+                    rel: "alternate".to_string(),
+                    ..Link::default()
+                }],
+                categories: project
+                    .skills
+                    .iter()
+                    .map(|skill| Category {
+                        term: skill.name.clone(),
+                        ..Category::default()
+                    })
+                    .collect(),
+                ..Entry::default()
+            }
+        })
+        .collect();
+
+    let updated = projects
+        .iter()
+        .map(|activity| activity.updated_at)
+        .max()
+        .unwrap_or(DateTime::UNIX_EPOCH);
+
+    let feed = Feed {
+        title: Text::plain(feed_title(profile, "Projects")),
+        id: "urn:portfolio-api:projects".to_string(),
+        updated: updated.fixed_offset(),
+        authors: non_empty(&profile.name)
+            .map(|name| Person {
+                name,
+                email: profile.email.clone(),
+                uri: profile.url.clone(),
+            })
+            .into_iter()
+            .collect(),
+        links: profile
+            .url
+            .iter()
+            .map(|url| Link {
+                href: url.clone(),
+                rel: "alternate".to_string(),
+                ..Link::default()
+            })
+            .collect(),
+        generator: Some(Generator {
+            value: "portfolio-api".to_string(),
+            ..Generator::default()
+        }),
+        entries,
+        ..Feed::default()
+    };
+    feed.to_string()
+}
+
+/// Renders recent portfolio activity as an RSS feed, newest first: projects added and
+/// updated, and jobs started, left and edited.
+pub fn activity_rss(
+    profile: &Profile,
+    site: &SiteConfig,
+    projects: &[ProjectActivity],
+    jobs: &[JobActivity],
+    limit: usize,
+) -> String {
+    let mut events: Vec<(DateTime<Utc>, rss::Item)> = Vec::new();
+
+    for activity in projects {
+        let project = &activity.project;
+        events.push((
+            activity.created_at,
+            project_item(
+                project,
+                site,
+                format!("New project: {}", project.name),
+                "created",
+            ),
+        ));
+        // Skip the bump from linking skills right after creating the project
+        if is_update(activity.created_at, activity.updated_at) {
+            let version = format!("updated:{}", activity.updated_at.timestamp());
+            events.push((
+                activity.updated_at,
+                project_item(
+                    project,
+                    site,
+                    format!("Updated project: {}", project.name),
+                    &version,
+                ),
+            ));
+        }
+    }
+
+    for activity in jobs {
+        let job = &activity.job;
+        let role = non_empty(&job.roles)
+            .map(|roles| format!(" as {}", roles))
+            .unwrap_or_default();
+        events.push((
+            start_of_day(job.start_date),
+            job_item(
+                activity,
+                format!("Started at {}{}", job.company_name, role),
+                "started",
+            ),
+        ));
+        if let Some(end_date) = job.end_date.filter(|_| !job.is_current_job) {
+            events.push((
+                start_of_day(end_date),
+                job_item(activity, format!("Left {}", job.company_name), "ended"),
+            ));
+        }
+        if is_update(activity.created_at, activity.updated_at) {
+            let version = format!("updated:{}", activity.updated_at.timestamp());
+            events.push((
+                activity.updated_at,
+                job_item(
+                    activity,
+                    format!("Updated job at {}", job.company_name),
+                    &version,
+                ),
+            ));
+        }
+    }
+
+    events.sort_by_key(|(date, _)| std::cmp::Reverse(*date));
+    events.truncate(limit);
+
+    let last_build_date = events.first().map(|(date, _)| date.to_rfc2822());
+    let items = events
+        .into_iter()
+        .map(|(date, item)| rss::Item {
+            pub_date: Some(date.to_rfc2822()),
+            ..item
+        })
+        .collect();
+
+    let channel = rss::Channel {
+        title: feed_title(profile, "Activity"),
+        link: profile.url.clone().unwrap_or_default(),
+        description: "New and updated projects and job changes".to_string(),
+        last_build_date,
+        generator: Some("portfolio-api".to_string()),
+        items,
+        ..rss::Channel::default()
+    };
+    channel.to_string()
+}
+
+/// Whether a record was changed after it was created, ignoring changes made within a
+/// minute of creating it
+fn is_update(created_at: DateTime<Utc>, updated_at: DateTime<Utc>) -> bool {
+    updated_at - created_at > chrono::Duration::minutes(1)
+}
+
+fn project_item(project: &Project, site: &SiteConfig, title: String, event: &str) -> rss::Item {
+    rss::Item {
+        title: Some(title),
+        link: Some(project_link(project, site)),
+        description: non_empty(&project.description),
+        categories: project
+            .skills
+            .iter()
+            .map(|skill| rss::Category {
+                name: skill.name.clone(),
+                domain: None,
+            })
+            .collect(),
+        guid: Some(rss::Guid {
+            value: format!("{}:{}", project_urn(project), event),
+            permalink: false,
+        }),
+        ..rss::Item::default()
+    }
+}
+
+fn job_item(activity: &JobActivity, title: String, event: &str) -> rss::Item {
+    let job = &activity.job;
+    rss::Item {
+        title: Some(title),
+        link: non_empty(&job.company_website),
+        description: non_empty(&job.description),
+        guid: Some(rss::Guid {
+            value: format!("urn:portfolio-api:job:{}:{}", job.id, event),
+            permalink: false,
+        }),
+        ..rss::Item::default()
+    }
+}
+
+/// The project's GitHub repository, or its page on the site without one
+fn project_link(project: &Project, site: &SiteConfig) -> String {
+    project
+        .github_url
+        .clone()
+        .unwrap_or_else(|| site.project_url(project.id))
+}
+
+fn project_urn(project: &Project) -> String {
+    format!("urn:portfolio-api:project:{}", project.id)
+}
+
+fn feed_title(profile: &Profile, feed: &str) -> String {
+    match non_empty(&profile.name) {
+        Some(name) => format!("{} \u{2013} {}", name, feed),
+        None => feed.to_string(),
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(chrono::NaiveTime::MIN).and_utc()
+}
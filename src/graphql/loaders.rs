@@ -0,0 +1,90 @@
+//! Dataloaders batching the lookups of nested fields into one query per field and
+//! level of the GraphQL query, instead of one per parent object.
+
+use crate::db::{jobs_db, projects_db, skills_db};
+use crate::models::job::Job;
+use crate::models::project::Project;
+use crate::models::skill::Skill;
+use async_graphql::dataloader::Loader;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Jobs by ID
+pub struct JobLoader(pub PgPool);
+
+/// Skills by ID
+pub struct SkillLoader(pub PgPool);
+
+/// Child skills by parent ID, ordered by name
+pub struct ChildSkillsLoader(pub PgPool);
+
+/// Projects by job ID, ordered by ID
+pub struct JobProjectsLoader(pub PgPool);
+
+/// Projects directly linked to a skill, by skill ID, ordered by ID
+pub struct SkillProjectsLoader(pub PgPool);
+
+impl Loader<i32> for JobLoader {
+    type Value = Job;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Job>, Self::Error> {
+        let jobs = jobs_db::fetch_jobs_by_ids(&self.0, keys).await?;
+        Ok(jobs.into_iter().map(|job| (job.id, job)).collect())
+    }
+}
+
+impl Loader<i32> for SkillLoader {
+    type Value = Skill;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Skill>, Self::Error> {
+        let skills = skills_db::fetch_skills_by_ids(&self.0, keys).await?;
+        Ok(skills.into_iter().map(|skill| (skill.id, skill)).collect())
+    }
+}
+
+impl Loader<i32> for ChildSkillsLoader {
+    type Value = Vec<Skill>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Vec<Skill>>, Self::Error> {
+        let skills = skills_db::fetch_skills_by_parent_ids(&self.0, keys).await?;
+        Ok(group_by(skills, |skill| skill.parent_id))
+    }
+}
+
+impl Loader<i32> for JobProjectsLoader {
+    type Value = Vec<Project>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Vec<Project>>, Self::Error> {
+        let projects = projects_db::fetch_projects_by_job_ids(&self.0, keys).await?;
+        Ok(group_by(projects, |project| project.job_id))
+    }
+}
+
+impl Loader<i32> for SkillProjectsLoader {
+    type Value = Vec<Project>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[i32]) -> Result<HashMap<i32, Vec<Project>>, Self::Error> {
+        let mut projects: HashMap<i32, Vec<Project>> = HashMap::new();
+        for (skill_id, project) in projects_db::fetch_projects_by_skill_ids(&self.0, keys).await? {
+            projects.entry(skill_id).or_default().push(project);
+        }
+        Ok(projects)
+    }
+}
+
+/// Groups values by a key, keeping their order within each group
+fn group_by<T>(values: Vec<T>, key: impl Fn(&T) -> Option<i32>) -> HashMap<i32, Vec<T>> {
+    let mut groups: HashMap<i32, Vec<T>> = HashMap::new();
+    for value in values {
+        if let Some(key) = key(&value) {
+            groups.entry(key).or_default().push(value);
+        }
+    }
+    groups
+}
@@ -0,0 +1,262 @@
+//! Read-only GraphQL API over jobs, projects and skills, so clients can fetch related
+//! records in one round trip.
+
+mod loaders;
+
+use crate::db::{jobs_db, projects_db, skills_db};
+use crate::models::job::Job;
+use crate::models::project::Project;
+use crate::models::skill::Skill;
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Enum, Error, Object, Result, Schema,
+};
+use chrono::NaiveDate;
+use loaders::{ChildSkillsLoader, JobLoader, JobProjectsLoader, SkillLoader, SkillProjectsLoader};
+use sqlx::PgPool;
+use std::fmt::Display;
+use tracing::error;
+
+/// Deepest field nesting a query may use; the skill hierarchy otherwise allows
+/// arbitrarily deep (and expensive) queries
+pub const MAX_QUERY_DEPTH: usize = 10;
+
+/// Highest complexity a query may have. Every field costs 1, and a list field costs
+/// `LIST_COMPLEXITY` times its selection, so nesting lists (e.g. `skills { projects {
+/// skills { ... } } }`) is bounded even though the output grows with every level.
+pub const MAX_QUERY_COMPLEXITY: usize = 1000;
+
+/// Assumed length of a list field when computing a query's complexity
+const LIST_COMPLEXITY: usize = 5;
+
+/// Most requests a batch may hold; each one is limited separately
+pub const MAX_BATCH_SIZE: usize = 10;
+
+pub type PortfolioSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Builds the schema, with dataloaders over `pool` for the nested fields
+pub fn build_schema(pool: PgPool) -> PortfolioSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(DataLoader::new(JobLoader(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(SkillLoader(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(
+            ChildSkillsLoader(pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            JobProjectsLoader(pool.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            SkillProjectsLoader(pool.clone()),
+            tokio::spawn,
+        ))
+        .data(pool)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Logs a database error and hides its details from the client, like the REST routes
+fn internal_error(e: impl Display) -> Error {
+    error!("GraphQL resolver failed: {}", e);
+    Error::new("An unexpected error occurred")
+}
+
+/// Proficiency level in a skill
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+#[graphql(
+    name = "Proficiency",
+    remote = "crate::db::proficiency_enum::Proficiency"
+)]
+enum GqlProficiency {
+    Beginner,
+    Intermediate,
+    Advanced,
+    Expert,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Every job, most recent first
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn jobs(&self, ctx: &Context<'_>) -> Result<Vec<Job>> {
+        jobs_db::fetch_all_jobs(ctx.data_unchecked())
+            .await
+            .map_err(internal_error)
+    }
+
+    async fn job(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Job>> {
+        let loader = ctx.data_unchecked::<DataLoader<JobLoader>>();
+        loader.load_one(id).await.map_err(internal_error)
+    }
+
+    /// Every project, by ID
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn projects(&self, ctx: &Context<'_>) -> Result<Vec<Project>> {
+        projects_db::fetch_all_projects(ctx.data_unchecked())
+            .await
+            .map_err(internal_error)
+    }
+
+    async fn project(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Project>> {
+        projects_db::fetch_project_by_id(ctx.data_unchecked(), id)
+            .await
+            .map_err(internal_error)
+    }
+
+    /// Every skill, by name
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn skills(&self, ctx: &Context<'_>) -> Result<Vec<Skill>> {
+        skills_db::fetch_all_skills(ctx.data_unchecked())
+            .await
+            .map_err(internal_error)
+    }
+
+    async fn skill(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Skill>> {
+        let loader = ctx.data_unchecked::<DataLoader<SkillLoader>>();
+        loader.load_one(id).await.map_err(internal_error)
+    }
+}
+
+/// A job in the portfolio
+#[Object]
+impl Job {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    /// Null for the current job
+    async fn end_date(&self) -> Option<NaiveDate> {
+        self.end_date
+    }
+
+    async fn is_current_job(&self) -> bool {
+        self.is_current_job
+    }
+
+    async fn company_name(&self) -> &str {
+        &self.company_name
+    }
+
+    async fn company_website(&self) -> &str {
+        &self.company_website
+    }
+
+    async fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn roles(&self) -> &str {
+        &self.roles
+    }
+
+    async fn responsibilities(&self) -> &str {
+        &self.responsibilities
+    }
+
+    /// Projects done for this job, by ID
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn projects(&self, ctx: &Context<'_>) -> Result<Vec<Project>> {
+        let loader = ctx.data_unchecked::<DataLoader<JobProjectsLoader>>();
+        let projects = loader.load_one(self.id).await.map_err(internal_error)?;
+        Ok(projects.unwrap_or_default())
+    }
+}
+
+/// A portfolio project
+#[Object]
+impl Project {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn github_url(&self) -> Option<&str> {
+        self.github_url.as_deref()
+    }
+
+    async fn job_id(&self) -> Option<i32> {
+        self.job_id
+    }
+
+    /// The job the project was done for
+    async fn job(&self, ctx: &Context<'_>) -> Result<Option<Job>> {
+        let Some(job_id) = self.job_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<DataLoader<JobLoader>>();
+        loader.load_one(job_id).await.map_err(internal_error)
+    }
+
+    /// Skills used by the project, by name
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn skills(&self) -> &[Skill] {
+        &self.skills
+    }
+}
+
+/// A technology or tool
+#[Object]
+impl Skill {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn official_site_url(&self) -> &str {
+        &self.official_site_url
+    }
+
+    async fn proficiency(&self) -> GqlProficiency {
+        self.proficiency.clone().into()
+    }
+
+    async fn parent_id(&self) -> Option<i32> {
+        self.parent_id
+    }
+
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Skill>> {
+        let Some(parent_id) = self.parent_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<DataLoader<SkillLoader>>();
+        loader.load_one(parent_id).await.map_err(internal_error)
+    }
+
+    /// Skills directly below this one, by name
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn children(&self, ctx: &Context<'_>) -> Result<Vec<Skill>> {
+        let loader = ctx.data_unchecked::<DataLoader<ChildSkillsLoader>>();
+        let children = loader.load_one(self.id).await.map_err(internal_error)?;
+        Ok(children.unwrap_or_default())
+    }
+
+    /// Projects that use this skill directly, by ID
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn projects(&self, ctx: &Context<'_>) -> Result<Vec<Project>> {
+        let loader = ctx.data_unchecked::<DataLoader<SkillProjectsLoader>>();
+        let projects = loader.load_one(self.id).await.map_err(internal_error)?;
+        Ok(projects.unwrap_or_default())
+    }
+}
@@ -0,0 +1,19 @@
+use crate::response_cache::{CacheStats, ResponseCache};
+use axum::Json;
+use axum::extract::State;
+
+/// Response cache statistics
+///
+/// Hit and miss counts of the in-memory response cache since startup, and whether
+/// it is currently invalidated by table change notifications or by TTL expiry
+#[utoipa::path(
+    get,
+    path = "/cache/stats",
+    responses(
+        (status = 200, description = "Cache statistics", body = CacheStats)
+    ),
+    tag = "cache"
+)]
+pub async fn get_cache_stats(State(cache): State<ResponseCache>) -> Json<CacheStats> {
+    Json(cache.stats())
+}
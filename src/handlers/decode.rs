@@ -0,0 +1,12 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+/// Query parameters selecting how database rows are decoded
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DecodeParams {
+    /// Return rows whose columns fail to decode instead of failing with 500; such
+    /// columns are `null` and each record carries a `warnings` list
+    #[serde(default)]
+    pub lenient: bool,
+}
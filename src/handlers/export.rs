@@ -0,0 +1,147 @@
+use crate::error::{ApiError, ProblemDetails};
+use crate::export::context::ResumeContext;
+use crate::export::json_resume::JsonResume;
+use crate::export::pdf::{PdfParams, render_pdf};
+use crate::export::text::{render_markdown, render_plain_text};
+use crate::export::{ResumeData, ResumeParams, ResumeSection};
+use crate::extract::ApiQuery;
+use crate::models::profile::Profile;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::{Json, extract::State};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+/// Export a JSON Resume
+///
+/// Returns the portfolio as a jsonresume.org document: jobs as `work`, top-level
+/// skills with their sub-skills as `skills`, projects as `projects`, and the configured
+/// profile as `basics`
+#[utoipa::path(
+    get,
+    path = "/export/json-resume",
+    responses(
+        (status = 200, description = "JSON Resume document", body = JsonResume),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "export"
+)]
+pub async fn get_json_resume(
+    State(pool): State<PgPool>,
+    State(profile): State<Arc<Profile>>,
+) -> Result<Json<JsonResume>, ApiError> {
+    let data = ResumeData::fetch(&pool).await?;
+    Ok(Json(JsonResume::new(&profile, &data)))
+}
+
+/// Export a PDF résumé
+///
+/// Renders the configured profile, jobs (most recent first), skills grouped under
+/// their top-level skill by proficiency, and projects as a PDF document
+#[utoipa::path(
+    get,
+    path = "/export/resume.pdf",
+    params(ResumeParams, PdfParams),
+    responses(
+        (status = 200, description = "PDF résumé", body = Vec<u8>, content_type = "application/pdf"),
+        (status = 400, description = "Invalid sections, project IDs or page size", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "export"
+)]
+pub async fn get_resume_pdf(
+    State(pool): State<PgPool>,
+    State(profile): State<Arc<Profile>>,
+    ApiQuery(params): ApiQuery<ResumeParams>,
+    ApiQuery(pdf_params): ApiQuery<PdfParams>,
+) -> Result<Response, ApiError> {
+    let (sections, data) = resume_data(&pool, &params).await?;
+    let pdf = render_pdf(&profile, &data, &sections, pdf_params.page_size);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf"),
+            (
+                header::CONTENT_DISPOSITION,
+                "inline; filename=\"resume.pdf\"",
+            ),
+        ],
+        pdf,
+    )
+        .into_response())
+}
+
+/// Export a Markdown résumé
+///
+/// Renders the same content as the PDF résumé as Markdown, for e.g. GitHub profile
+/// READMEs; projects link to their GitHub repositories
+#[utoipa::path(
+    get,
+    path = "/export/resume.md",
+    params(ResumeParams),
+    responses(
+        (status = 200, description = "Markdown résumé", body = String, content_type = "text/markdown"),
+        (status = 400, description = "Invalid sections or project IDs", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "export"
+)]
+pub async fn get_resume_markdown(
+    State(pool): State<PgPool>,
+    State(profile): State<Arc<Profile>>,
+    ApiQuery(params): ApiQuery<ResumeParams>,
+) -> Result<Response, ApiError> {
+    let (sections, data) = resume_data(&pool, &params).await?;
+    let markdown = render_markdown(&ResumeContext::new(&profile, &data, &sections))?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+        markdown,
+    )
+        .into_response())
+}
+
+/// Export a plain-text résumé
+///
+/// Renders the same content as the PDF résumé as plain text, for pasting into job
+/// boards
+#[utoipa::path(
+    get,
+    path = "/export/resume.txt",
+    params(ResumeParams),
+    responses(
+        (status = 200, description = "Plain-text résumé", body = String, content_type = "text/plain"),
+        (status = 400, description = "Invalid sections or project IDs", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "export"
+)]
+pub async fn get_resume_text(
+    State(pool): State<PgPool>,
+    State(profile): State<Arc<Profile>>,
+    ApiQuery(params): ApiQuery<ResumeParams>,
+) -> Result<Response, ApiError> {
+    let (sections, data) = resume_data(&pool, &params).await?;
+    let text = render_plain_text(&ResumeContext::new(&profile, &data, &sections))?;
+
+    Ok(([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], text).into_response())
+}
+
+/// Validates the résumé parameters and fetches the data they select
+pub async fn resume_data(
+    pool: &PgPool,
+    params: &ResumeParams,
+) -> Result<(Vec<ResumeSection>, ResumeData), ApiError> {
+    let bad_request = |detail| ApiError::InvalidRequest {
+        status: StatusCode::BAD_REQUEST,
+        detail,
+    };
+    let sections = params.sections().map_err(bad_request)?;
+    let project_ids = params.project_ids().map_err(bad_request)?;
+
+    let mut data = ResumeData::fetch(pool).await?;
+    if let Some(project_ids) = project_ids {
+        data.retain_projects(&project_ids);
+    }
+    Ok((sections, data))
+}
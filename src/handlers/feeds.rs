@@ -0,0 +1,70 @@
+use crate::db::{jobs_db, projects_db};
+use crate::error::{ApiError, ProblemDetails};
+use crate::feeds::{activity_rss, projects_atom};
+use crate::models::feed::FEED_ENTRY_LIMIT;
+use crate::models::profile::Profile;
+use crate::seo::SiteConfig;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+/// Atom feed of projects
+///
+/// The 50 most recently changed projects, linking to their GitHub repositories (or
+/// their pages on the site) with their skills as categories
+#[utoipa::path(
+    get,
+    path = "/feeds/projects.atom",
+    responses(
+        (status = 200, description = "Atom feed", body = String, content_type = "application/atom+xml"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "feeds"
+)]
+pub async fn get_projects_feed(
+    State(pool): State<PgPool>,
+    State(profile): State<Arc<Profile>>,
+    State(site): State<Arc<SiteConfig>>,
+) -> Result<Response, ApiError> {
+    let projects = projects_db::fetch_project_activity(&pool, FEED_ENTRY_LIMIT).await?;
+    let feed = projects_atom(&profile, &site, &projects);
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+        feed,
+    )
+        .into_response())
+}
+
+/// RSS feed of portfolio activity
+///
+/// The 50 latest events, newest first: projects added or updated, and jobs started,
+/// left or edited
+#[utoipa::path(
+    get,
+    path = "/feeds/activity.rss",
+    responses(
+        (status = 200, description = "RSS feed", body = String, content_type = "application/rss+xml"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "feeds"
+)]
+pub async fn get_activity_feed(
+    State(pool): State<PgPool>,
+    State(profile): State<Arc<Profile>>,
+    State(site): State<Arc<SiteConfig>>,
+) -> Result<Response, ApiError> {
+    let (projects, jobs) = tokio::try_join!(
+        projects_db::fetch_project_activity(&pool, FEED_ENTRY_LIMIT),
+        jobs_db::fetch_job_activity(&pool),
+    )?;
+    let feed = activity_rss(&profile, &site, &projects, &jobs, FEED_ENTRY_LIMIT as usize);
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+        feed,
+    )
+        .into_response())
+}
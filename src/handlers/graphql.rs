@@ -0,0 +1,60 @@
+use crate::error::{ApiError, ProblemDetails};
+use crate::extract::ApiJson;
+use crate::graphql::{MAX_BATCH_SIZE, PortfolioSchema};
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{BatchRequest, BatchResponse};
+use axum::Json;
+use axum::extract::State;
+use axum::response::Html;
+
+/// Execute a GraphQL query
+///
+/// Read-only GraphQL API over jobs, projects and skills. Accepts a single request
+/// (`{"query": ..., "variables": ..., "operationName": ...}`) or an array of up to 10.
+/// Query errors, including queries nested too deeply or too complex, are reported in
+/// the response's `errors`, with status 200
+#[utoipa::path(
+    post,
+    path = "/graphql",
+    request_body(content = Object, description = "GraphQL request or array of requests", content_type = "application/json"),
+    responses(
+        (status = 200, description = "GraphQL response or array of responses", body = Object),
+        (status = 400, description = "Malformed JSON body", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Body is not a GraphQL request, or the batch is too large", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "graphql"
+)]
+pub async fn graphql(
+    State(schema): State<PortfolioSchema>,
+    ApiJson(request): ApiJson<BatchRequest>,
+) -> Result<Json<BatchResponse>, ApiError> {
+    if let BatchRequest::Batch(requests) = &request
+        && requests.len() > MAX_BATCH_SIZE
+    {
+        return Err(ApiError::Validation(format!(
+            "A batch may hold at most {} requests",
+            MAX_BATCH_SIZE
+        )));
+    }
+    Ok(Json(schema.execute_batch(request).await))
+}
+
+/// GraphiQL
+///
+/// In-browser IDE for exploring and querying the GraphQL API
+#[utoipa::path(
+    get,
+    path = "/graphql",
+    responses(
+        (status = 200, description = "GraphiQL page", body = String, content_type = "text/html")
+    ),
+    tag = "graphql"
+)]
+pub async fn graphiql() -> Html<String> {
+    Html(
+        GraphiQLSource::build()
+            .endpoint("/graphql")
+            .title("Portfolio API GraphiQL")
+            .finish(),
+    )
+}
@@ -0,0 +1,46 @@
+use crate::health::{Health, HealthReport};
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use sqlx::PgPool;
+
+/// Liveness probe
+///
+/// Passes whenever the process answers, including during graceful shutdown, and
+/// never touches the database
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    responses(
+        (status = 200, description = "The process is live", body = HealthReport)
+    ),
+    tag = "health"
+)]
+pub async fn get_healthz(State(health): State<Health>) -> Json<HealthReport> {
+    Json(health.liveness())
+}
+
+/// Readiness probe
+///
+/// Checks that the server is not shutting down, that a pooled connection runs
+/// `SELECT 1` and that every embedded migration is applied, each within the readiness
+/// timeout, reporting the status and duration of every check
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    responses(
+        (status = 200, description = "Ready to serve requests", body = HealthReport),
+        (status = 503, description = "Not ready; the failing checks say why", body = HealthReport)
+    ),
+    tag = "health"
+)]
+pub async fn get_readyz(State(health): State<Health>, State(pool): State<PgPool>) -> Response {
+    let report = health.readiness(&pool).await;
+    let status = if report.passed() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
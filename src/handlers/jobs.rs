@@ -1,30 +1,56 @@
 use crate::db::jobs_db;
-use crate::models::job::Job;
-use axum::response::IntoResponse;
-use axum::{Json, extract::State, http::StatusCode};
+use crate::db::lenient::Lenient;
+use crate::error::{ApiError, ProblemDetails};
+use crate::extract::{ApiJson, ApiPath, ApiQuery};
+use crate::handlers::decode::DecodeParams;
+use crate::handlers::page::{page_request, page_response};
+use crate::models::job::{Job, JobFilters, JobPatch, NewJob};
+use crate::models::page::{Page, PageParams};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::{Json, extract::OriginalUri, extract::State, http::StatusCode};
 use sqlx::PgPool;
-use tracing::error;
 
 /// Get all jobs
 ///
 /// Returns a list of all jobs in the portfolio
+///
+/// Sortable by `start_date`, `company_name` and `id`; defaults to `-start_date`
 #[utoipa::path(
     get,
     path = "/jobs",
     responses(
-        (status = 200, description = "List of jobs retrieved successfully", body = Vec<Job>),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "List of jobs retrieved successfully", body = Page<Job>, headers(("Link" = String, description = "`<...>; rel=\"next\"` link to the next page, when there is one"))),
+        (status = 400, description = "Invalid limit, sort or cursor", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        DecodeParams,
+        PageParams,
+        JobFilters
     ),
     tag = "jobs"
 )]
-pub async fn get_jobs(State(pool): State<PgPool>) -> impl IntoResponse {
-    match jobs_db::fetch_jobs(&pool).await {
-        Ok(jobs) => (StatusCode::OK, Json(jobs)).into_response(),
-        Err(e) => {
-            error!("Failed to get jobs: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch jobs").into_response()
-        }
+pub async fn get_jobs(
+    State(pool): State<PgPool>,
+    OriginalUri(uri): OriginalUri,
+    ApiQuery(decode): ApiQuery<DecodeParams>,
+    ApiQuery(page_params): ApiQuery<PageParams>,
+    ApiQuery(filters): ApiQuery<JobFilters>,
+) -> Result<Response, ApiError> {
+    let page = page_request(
+        &page_params,
+        jobs_db::JOB_SORT_KEYS,
+        jobs_db::JOB_DEFAULT_SORT,
+    )?;
+
+    if decode.lenient {
+        let jobs = jobs_db::fetch_jobs::<Lenient<Job>>(&pool, &filters, &page).await?;
+        return Ok(page_response(&uri, jobs));
     }
+
+    let jobs = jobs_db::fetch_jobs::<Job>(&pool, &filters, &page).await?;
+    Ok(page_response(&uri, jobs))
 }
 
 /// Get a single job by ID
@@ -35,24 +61,160 @@ pub async fn get_jobs(State(pool): State<PgPool>) -> impl IntoResponse {
     path = "/jobs/{job_id}",
     responses(
         (status = 200, description = "Job retrieved successfully", body = Job),
-        (status = 404, description = "Job not found"),
-        (status = 500, description = "Internal server error")
+        (status = 404, description = "Job not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
     ),
     params(
-        ("job_id" = i32, Path, description = "ID of the job to retrieve")
+        ("job_id" = i32, Path, description = "ID of the job to retrieve"),
+        DecodeParams
     ),
     tag = "jobs"
 )]
 pub async fn get_job_by_id(
     State(pool): State<PgPool>,
-    axum::extract::Path(job_id): axum::extract::Path<i32>,
-) -> impl IntoResponse {
-    match jobs_db::fetch_job_by_id(&pool, job_id).await {
-        Ok(Some(job)) => (StatusCode::OK, Json(job)).into_response(),
-        Ok(None) => (StatusCode::NOT_FOUND, "Job not found").into_response(),
-        Err(e) => {
-            error!("Failed to get job: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch job").into_response()
-        }
+    ApiPath(job_id): ApiPath<i32>,
+    ApiQuery(decode): ApiQuery<DecodeParams>,
+) -> Result<Response, ApiError> {
+    let job = if decode.lenient {
+        jobs_db::fetch_job_by_id::<Lenient<Job>>(&pool, job_id)
+            .await?
+            .map(|job| Json(job).into_response())
+    } else {
+        jobs_db::fetch_job_by_id::<Job>(&pool, job_id)
+            .await?
+            .map(|job| Json(job).into_response())
+    };
+
+    job.ok_or_else(|| ApiError::NotFound("Job not found".to_string()))
+}
+
+/// Create a job
+///
+/// Validates the payload and returns the created job with a `Location` header
+#[utoipa::path(
+    post,
+    path = "/jobs",
+    request_body = NewJob,
+    responses(
+        (status = 201, description = "Job created successfully", body = Job),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid job payload", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "jobs",
+    security(("bearer_auth" = ["jobs:write"]))
+)]
+pub async fn create_job(
+    State(pool): State<PgPool>,
+    ApiJson(new_job): ApiJson<NewJob>,
+) -> Result<impl IntoResponse, ApiError> {
+    new_job.validate().map_err(ApiError::Validation)?;
+
+    let job = jobs_db::create_job(&pool, &new_job).await?;
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, format!("/jobs/{}", job.id))],
+        Json(job),
+    ))
+}
+
+/// Replace a job
+///
+/// Overwrites every field of an existing job, or returns 404 if not found
+#[utoipa::path(
+    put,
+    path = "/jobs/{job_id}",
+    request_body = NewJob,
+    responses(
+        (status = 200, description = "Job updated successfully", body = Job),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Job not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid job payload", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("job_id" = i32, Path, description = "ID of the job to replace")
+    ),
+    tag = "jobs",
+    security(("bearer_auth" = ["jobs:write"]))
+)]
+pub async fn update_job(
+    State(pool): State<PgPool>,
+    ApiPath(job_id): ApiPath<i32>,
+    ApiJson(job): ApiJson<NewJob>,
+) -> Result<Json<Job>, ApiError> {
+    job.validate().map_err(ApiError::Validation)?;
+
+    jobs_db::update_job(&pool, job_id, &job)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound("Job not found".to_string()))
+}
+
+/// Partially update a job
+///
+/// Applies only the supplied fields and re-validates the resulting job
+#[utoipa::path(
+    patch,
+    path = "/jobs/{job_id}",
+    request_body = JobPatch,
+    responses(
+        (status = 200, description = "Job updated successfully", body = Job),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Job not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid job payload", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("job_id" = i32, Path, description = "ID of the job to update")
+    ),
+    tag = "jobs",
+    security(("bearer_auth" = ["jobs:write"]))
+)]
+pub async fn patch_job(
+    State(pool): State<PgPool>,
+    ApiPath(job_id): ApiPath<i32>,
+    ApiJson(patch): ApiJson<JobPatch>,
+) -> Result<Json<Job>, ApiError> {
+    jobs_db::patch_job(&pool, job_id, patch)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound("Job not found".to_string()))
+}
+
+/// Delete a job
+///
+/// Returns 409 if projects still reference the job
+#[utoipa::path(
+    delete,
+    path = "/jobs/{job_id}",
+    responses(
+        (status = 204, description = "Job deleted successfully"),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Job not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Job is still referenced by projects", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("job_id" = i32, Path, description = "ID of the job to delete")
+    ),
+    tag = "jobs",
+    security(("bearer_auth" = ["jobs:write"]))
+)]
+pub async fn delete_job(
+    State(pool): State<PgPool>,
+    ApiPath(job_id): ApiPath<i32>,
+) -> Result<StatusCode, ApiError> {
+    match jobs_db::delete_job(&pool, job_id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::NotFound("Job not found".to_string())),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => Err(ApiError::Conflict(
+            "Job is still referenced by projects".to_string(),
+        )),
+        Err(e) => Err(e.into()),
     }
 }
@@ -0,0 +1,26 @@
+use crate::error::{ApiError, ProblemDetails};
+use crate::metrics::METRICS;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use sqlx::PgPool;
+
+/// Prometheus metrics
+///
+/// Request counts and latencies per route template and status class, database
+/// function durations and connection pool statistics, in the Prometheus text format
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String, content_type = "text/plain; version=0.0.4"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "metrics"
+)]
+pub async fn get_metrics(State(pool): State<PgPool>) -> Result<Response, ApiError> {
+    let metrics = METRICS
+        .render(&pool)
+        .map_err(|e| ApiError::Internal(format!("failed to encode metrics: {}", e)))?;
+    Ok(([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], metrics).into_response())
+}
@@ -1,3 +1,14 @@
+pub mod cache;
+pub mod decode;
+pub mod export;
+pub mod feeds;
+pub mod graphql;
+pub mod health;
 pub mod jobs;
+pub mod metrics;
+pub mod page;
 pub mod projects;
+pub mod render;
+pub mod search;
+pub mod seo;
 pub mod skills;
@@ -0,0 +1,43 @@
+use crate::db::pagination::{PageRequest, SortKey};
+use crate::error::ApiError;
+use crate::models::page::{Page, PageParams};
+use axum::Json;
+use axum::http::{HeaderValue, StatusCode, Uri, header};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+
+/// Validates pagination parameters, rejecting bad ones with 400
+pub fn page_request(
+    params: &PageParams,
+    keys: &'static [SortKey],
+    default_sort: &str,
+) -> Result<PageRequest, ApiError> {
+    params
+        .resolve(keys, default_sort)
+        .map_err(|detail| ApiError::InvalidRequest {
+            status: StatusCode::BAD_REQUEST,
+            detail,
+        })
+}
+
+/// Returns the page as JSON, with a `Link: rel="next"` header when another page follows.
+///
+/// The next link is the request URI with its `cursor` replaced, so filters and
+/// other parameters carry over.
+pub fn page_response<T: Serialize>(uri: &Uri, page: Page<T>) -> Response {
+    let link = page.next_cursor.as_deref().map(|cursor| {
+        let pairs = url::form_urlencoded::parse(uri.query().unwrap_or("").as_bytes())
+            .filter(|(name, _)| name != "cursor");
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .append_pair("cursor", cursor)
+            .finish();
+        format!("<{}?{}>; rel=\"next\"", uri.path(), query)
+    });
+
+    let mut response = Json(page).into_response();
+    if let Some(link) = link.and_then(|link| HeaderValue::from_str(&link).ok()) {
+        response.headers_mut().insert(header::LINK, link);
+    }
+    response
+}
@@ -1,34 +1,64 @@
+use crate::db::lenient::Lenient;
 use crate::db::projects_db;
-use crate::models::project::Project;
-use axum::response::IntoResponse;
-use axum::{Json, extract::State, http::StatusCode};
+use crate::error::{ApiError, ProblemDetails};
+use crate::extract::{ApiJson, ApiPath, ApiQuery};
+use crate::handlers::decode::DecodeParams;
+use crate::handlers::page::{page_request, page_response};
+use crate::models::page::{Page, PageParams};
+use crate::models::project::{
+    MatchedProject, NewProject, Project, ProjectFilters, ProjectPatch, ProjectSkillIds,
+    SkillMatchParams,
+};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::{Json, extract::OriginalUri, extract::State, http::StatusCode};
 use sqlx::PgPool;
-use tracing::error;
+
+fn project_not_found() -> ApiError {
+    ApiError::NotFound("Project not found".to_string())
+}
 
 /// Get all projects
 ///
 /// Returns a list of all projects in the portfolio
+///
+/// Sortable by `name` and `id`; defaults to `id`
 #[utoipa::path(
     get,
     path = "/projects",
     responses(
-        (status = 200, description = "List of projects retrieved successfully", body = Vec<Project>),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "List of projects retrieved successfully", body = Page<Project>, headers(("Link" = String, description = "`<...>; rel=\"next\"` link to the next page, when there is one"))),
+        (status = 400, description = "Invalid limit, sort or cursor", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        DecodeParams,
+        PageParams,
+        ProjectFilters
     ),
     tag = "projects"
 )]
-pub async fn get_projects(State(pool): State<PgPool>) -> impl IntoResponse {
-    match projects_db::fetch_projects(&pool).await {
-        Ok(projects) => (StatusCode::OK, Json(projects)).into_response(),
-        Err(e) => {
-            error!("Failed to get projects: {:?}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to fetch projects",
-            )
-                .into_response()
-        }
+pub async fn get_projects(
+    State(pool): State<PgPool>,
+    OriginalUri(uri): OriginalUri,
+    ApiQuery(decode): ApiQuery<DecodeParams>,
+    ApiQuery(page_params): ApiQuery<PageParams>,
+    ApiQuery(filters): ApiQuery<ProjectFilters>,
+) -> Result<Response, ApiError> {
+    let page = page_request(
+        &page_params,
+        projects_db::PROJECT_SORT_KEYS,
+        projects_db::PROJECT_DEFAULT_SORT,
+    )?;
+
+    if decode.lenient {
+        let projects =
+            projects_db::fetch_projects::<Lenient<Project>>(&pool, &filters, &page).await?;
+        return Ok(page_response(&uri, projects));
     }
+
+    let projects = projects_db::fetch_projects::<Project>(&pool, &filters, &page).await?;
+    Ok(page_response(&uri, projects))
 }
 
 /// Get a single project by ID
@@ -39,88 +69,355 @@ pub async fn get_projects(State(pool): State<PgPool>) -> impl IntoResponse {
     path = "/projects/{project_id}",
     responses(
         (status = 200, description = "Project retrieved successfully", body = Project),
-        (status = 404, description = "Project not found"),
-        (status = 500, description = "Internal server error")
+        (status = 404, description = "Project not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
     ),
     params(
-        ("project_id" = i32, Path, description = "ID of the project to retrieve")
+        ("project_id" = i32, Path, description = "ID of the project to retrieve"),
+        DecodeParams
     ),
     tag = "projects"
 )]
 pub async fn get_project_by_id(
     State(pool): State<PgPool>,
-    axum::extract::Path(project_id): axum::extract::Path<i32>,
-) -> impl IntoResponse {
-    match projects_db::fetch_project_by_id(&pool, project_id).await {
-        Ok(Some(project)) => (StatusCode::OK, Json(project)).into_response(),
-        Ok(None) => (StatusCode::NOT_FOUND, "Project not found").into_response(),
-        Err(e) => {
-            error!("Failed to get project: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch project").into_response()
-        }
-    }
+    ApiPath(project_id): ApiPath<i32>,
+    ApiQuery(decode): ApiQuery<DecodeParams>,
+) -> Result<Response, ApiError> {
+    let project = if decode.lenient {
+        projects_db::fetch_project_by_id::<Lenient<Project>>(&pool, project_id)
+            .await?
+            .map(|project| Json(project).into_response())
+    } else {
+        projects_db::fetch_project_by_id::<Project>(&pool, project_id)
+            .await?
+            .map(|project| Json(project).into_response())
+    };
+
+    project.ok_or_else(project_not_found)
 }
 
 /// Get all projects for a specific job
 ///
 /// Returns a list of all projects associated with the specified job
+///
+/// Sortable by `name` and `id`; defaults to `id`
 #[utoipa::path(
     get,
     path = "/projects/job/{job_id}",
     responses(
-        (status = 200, description = "List of projects retrieved successfully", body = Vec<Project>),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "List of projects retrieved successfully", body = Page<Project>, headers(("Link" = String, description = "`<...>; rel=\"next\"` link to the next page, when there is one"))),
+        (status = 400, description = "Invalid limit, sort or cursor", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
     ),
     params(
-        ("job_id" = i32, Path, description = "ID of the job to fetch projects for")
+        ("job_id" = i32, Path, description = "ID of the job to fetch projects for"),
+        DecodeParams,
+        PageParams
     ),
     tag = "projects"
 )]
 pub async fn get_projects_by_job(
     State(pool): State<PgPool>,
-    axum::extract::Path(job_id): axum::extract::Path<i32>,
-) -> impl IntoResponse {
-    match projects_db::fetch_projects_by_job(&pool, job_id).await {
-        Ok(projects) => (StatusCode::OK, Json(projects)).into_response(),
-        Err(e) => {
-            error!("Failed to get projects for job {}: {:?}", job_id, e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to fetch projects",
-            )
-                .into_response()
-        }
+    ApiPath(job_id): ApiPath<i32>,
+    OriginalUri(uri): OriginalUri,
+    ApiQuery(decode): ApiQuery<DecodeParams>,
+    ApiQuery(page_params): ApiQuery<PageParams>,
+) -> Result<Response, ApiError> {
+    let page = page_request(
+        &page_params,
+        projects_db::PROJECT_SORT_KEYS,
+        projects_db::PROJECT_DEFAULT_SORT,
+    )?;
+
+    if decode.lenient {
+        let projects =
+            projects_db::fetch_projects_by_job::<Lenient<Project>>(&pool, job_id, &page).await?;
+        return Ok(page_response(&uri, projects));
     }
+
+    let projects = projects_db::fetch_projects_by_job::<Project>(&pool, job_id, &page).await?;
+    Ok(page_response(&uri, projects))
 }
 
 /// Get all projects that use a specific skill
 ///
-/// Returns a list of all projects that use the specified skill
+/// Returns a list of all projects that use the specified skill, or with
+/// `include_descendants` any of its sub-skills. Each project lists the IDs of the
+/// skills that matched.
+///
+/// Sortable by `name` and `id`; defaults to `id`
 #[utoipa::path(
     get,
     path = "/projects/skill/{skill_id}",
     responses(
-        (status = 200, description = "List of projects retrieved successfully", body = Vec<Project>),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "List of projects retrieved successfully", body = Page<MatchedProject>, headers(("Link" = String, description = "`<...>; rel=\"next\"` link to the next page, when there is one"))),
+        (status = 400, description = "Invalid limit, sort or cursor", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
     ),
     params(
-        ("skill_id" = i32, Path, description = "ID of the skill to fetch projects for")
+        ("skill_id" = i32, Path, description = "ID of the skill to fetch projects for"),
+        DecodeParams,
+        PageParams,
+        SkillMatchParams
     ),
     tag = "projects"
 )]
 pub async fn get_projects_by_skill(
     State(pool): State<PgPool>,
-    axum::extract::Path(skill_id): axum::extract::Path<i32>,
-) -> impl IntoResponse {
-    match projects_db::fetch_projects_by_skill(&pool, skill_id).await {
-        Ok(projects) => (StatusCode::OK, Json(projects)).into_response(),
-        Err(e) => {
-            error!("Failed to get projects for skill {}: {:?}", skill_id, e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to fetch projects",
-            )
-                .into_response()
-        }
+    ApiPath(skill_id): ApiPath<i32>,
+    OriginalUri(uri): OriginalUri,
+    ApiQuery(decode): ApiQuery<DecodeParams>,
+    ApiQuery(page_params): ApiQuery<PageParams>,
+    ApiQuery(matching): ApiQuery<SkillMatchParams>,
+) -> Result<Response, ApiError> {
+    let page = page_request(
+        &page_params,
+        projects_db::PROJECT_SORT_KEYS,
+        projects_db::PROJECT_DEFAULT_SORT,
+    )?;
+    let include_descendants = matching.include_descendants;
+
+    if decode.lenient {
+        let projects = projects_db::fetch_projects_by_skill::<Lenient<MatchedProject>>(
+            &pool,
+            skill_id,
+            include_descendants,
+            &page,
+        )
+        .await?;
+        return Ok(page_response(&uri, projects));
+    }
+
+    let projects = projects_db::fetch_projects_by_skill::<MatchedProject>(
+        &pool,
+        skill_id,
+        include_descendants,
+        &page,
+    )
+    .await?;
+    Ok(page_response(&uri, projects))
+}
+
+/// Create a project
+///
+/// Creates the project and its skill links together and returns the hydrated project
+#[utoipa::path(
+    post,
+    path = "/projects",
+    request_body = NewProject,
+    responses(
+        (status = 201, description = "Project created successfully", body = Project),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload or unknown job/skill ID", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "projects",
+    security(("bearer_auth" = ["projects:write"]))
+)]
+pub async fn create_project(
+    State(pool): State<PgPool>,
+    ApiJson(new_project): ApiJson<NewProject>,
+) -> Result<impl IntoResponse, ApiError> {
+    new_project.validate().map_err(ApiError::Validation)?;
+
+    let project = projects_db::create_project(&pool, &new_project)
+        .await?
+        .ok_or_else(|| ApiError::Internal("Created project could not be read back".to_string()))?;
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, format!("/projects/{}", project.id))],
+        Json(project),
+    ))
+}
+
+/// Replace a project
+///
+/// Overwrites every field of the project and replaces its skill links
+#[utoipa::path(
+    put,
+    path = "/projects/{project_id}",
+    request_body = NewProject,
+    responses(
+        (status = 200, description = "Project updated successfully", body = Project),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Project not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload or unknown job/skill ID", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("project_id" = i32, Path, description = "ID of the project to replace")
+    ),
+    tag = "projects",
+    security(("bearer_auth" = ["projects:write"]))
+)]
+pub async fn update_project(
+    State(pool): State<PgPool>,
+    ApiPath(project_id): ApiPath<i32>,
+    ApiJson(project): ApiJson<NewProject>,
+) -> Result<Json<Project>, ApiError> {
+    project.validate().map_err(ApiError::Validation)?;
+
+    projects_db::update_project(&pool, project_id, &project)
+        .await?
+        .map(Json)
+        .ok_or_else(project_not_found)
+}
+
+/// Partially update a project
+///
+/// Applies only the supplied fields; skill links are replaced only when `skill_ids` is sent
+#[utoipa::path(
+    patch,
+    path = "/projects/{project_id}",
+    request_body = ProjectPatch,
+    responses(
+        (status = 200, description = "Project updated successfully", body = Project),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Project not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload or unknown job/skill ID", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("project_id" = i32, Path, description = "ID of the project to update")
+    ),
+    tag = "projects",
+    security(("bearer_auth" = ["projects:write"]))
+)]
+pub async fn patch_project(
+    State(pool): State<PgPool>,
+    ApiPath(project_id): ApiPath<i32>,
+    ApiJson(patch): ApiJson<ProjectPatch>,
+) -> Result<Json<Project>, ApiError> {
+    patch.validate().map_err(ApiError::Validation)?;
+
+    projects_db::patch_project(&pool, project_id, patch)
+        .await?
+        .map(Json)
+        .ok_or_else(project_not_found)
+}
+
+/// Delete a project
+///
+/// Deletes the project together with its skill links
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}",
+    responses(
+        (status = 204, description = "Project deleted successfully"),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Project not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("project_id" = i32, Path, description = "ID of the project to delete")
+    ),
+    tag = "projects",
+    security(("bearer_auth" = ["projects:write"]))
+)]
+pub async fn delete_project(
+    State(pool): State<PgPool>,
+    ApiPath(project_id): ApiPath<i32>,
+) -> Result<StatusCode, ApiError> {
+    if projects_db::delete_project(&pool, project_id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(project_not_found())
     }
 }
+
+/// Replace the skills linked to a project
+///
+/// Returns the project with its new skill set
+#[utoipa::path(
+    put,
+    path = "/projects/{project_id}/skills",
+    request_body = ProjectSkillIds,
+    responses(
+        (status = 200, description = "Project skills replaced successfully", body = Project),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Project not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Unknown skill ID", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("project_id" = i32, Path, description = "ID of the project to update")
+    ),
+    tag = "projects",
+    security(("bearer_auth" = ["projects:write"]))
+)]
+pub async fn replace_project_skills(
+    State(pool): State<PgPool>,
+    ApiPath(project_id): ApiPath<i32>,
+    ApiJson(body): ApiJson<ProjectSkillIds>,
+) -> Result<Json<Project>, ApiError> {
+    projects_db::replace_project_skills(&pool, project_id, &body.skill_ids)
+        .await?
+        .map(Json)
+        .ok_or_else(project_not_found)
+}
+
+/// Link a skill to a project
+///
+/// Linking an already linked skill leaves the project unchanged
+#[utoipa::path(
+    post,
+    path = "/projects/{project_id}/skills/{skill_id}",
+    responses(
+        (status = 200, description = "Skill linked successfully", body = Project),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Project not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Unknown skill ID", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("project_id" = i32, Path, description = "ID of the project to update"),
+        ("skill_id" = i32, Path, description = "ID of the skill to link")
+    ),
+    tag = "projects",
+    security(("bearer_auth" = ["projects:write"]))
+)]
+pub async fn add_project_skill(
+    State(pool): State<PgPool>,
+    ApiPath((project_id, skill_id)): ApiPath<(i32, i32)>,
+) -> Result<Json<Project>, ApiError> {
+    projects_db::add_project_skill(&pool, project_id, skill_id)
+        .await?
+        .map(Json)
+        .ok_or_else(project_not_found)
+}
+
+/// Unlink a skill from a project
+///
+/// Unlinking a skill that is not linked leaves the project unchanged
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}/skills/{skill_id}",
+    responses(
+        (status = 200, description = "Skill unlinked successfully", body = Project),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Project not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("project_id" = i32, Path, description = "ID of the project to update"),
+        ("skill_id" = i32, Path, description = "ID of the skill to unlink")
+    ),
+    tag = "projects",
+    security(("bearer_auth" = ["projects:write"]))
+)]
+pub async fn remove_project_skill(
+    State(pool): State<PgPool>,
+    ApiPath((project_id, skill_id)): ApiPath<(i32, i32)>,
+) -> Result<Json<Project>, ApiError> {
+    projects_db::remove_project_skill(&pool, project_id, skill_id)
+        .await?
+        .map(Json)
+        .ok_or_else(project_not_found)
+}
@@ -0,0 +1,51 @@
+use crate::error::{ApiError, ProblemDetails};
+use crate::export::ResumeParams;
+use crate::export::context::ResumeContext;
+use crate::extract::{ApiPath, ApiQuery};
+use crate::handlers::export::resume_data;
+use crate::models::profile::Profile;
+use crate::render::Themes;
+use axum::extract::State;
+use axum::response::Html;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+/// Render a portfolio page
+///
+/// Renders the profile, jobs, skills and projects as a complete HTML page with the
+/// given theme. `classic` and `minimal` are built in; more can be loaded from
+/// `THEMES_DIR` at startup
+#[utoipa::path(
+    get,
+    path = "/render/{theme}",
+    params(
+        ("theme" = String, Path, description = "Theme name, e.g. `classic` or `minimal`"),
+        ResumeParams
+    ),
+    responses(
+        (status = 200, description = "HTML page", body = String, content_type = "text/html"),
+        (status = 400, description = "Invalid sections or project IDs", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Theme not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "render"
+)]
+pub async fn render_page(
+    State(pool): State<PgPool>,
+    State(profile): State<Arc<Profile>>,
+    State(themes): State<Arc<Themes>>,
+    ApiPath(theme): ApiPath<String>,
+    ApiQuery(params): ApiQuery<ResumeParams>,
+) -> Result<Html<String>, ApiError> {
+    if !themes.contains(&theme) {
+        return Err(ApiError::NotFound(format!(
+            "Theme `{}` does not exist; available themes: {}",
+            theme,
+            themes.names().collect::<Vec<_>>().join(", ")
+        )));
+    }
+
+    let (sections, data) = resume_data(&pool, &params).await?;
+    let page = themes.render(&theme, &ResumeContext::new(&profile, &data, &sections))?;
+    Ok(Html(page))
+}
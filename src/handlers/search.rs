@@ -0,0 +1,39 @@
+use crate::db::search_db;
+use crate::error::{ApiError, ProblemDetails};
+use crate::extract::ApiQuery;
+use crate::models::search::{SearchHit, SearchParams};
+use axum::{Json, extract::State, http::StatusCode};
+use sqlx::PgPool;
+
+/// Search the portfolio
+///
+/// Full-text search across project names and descriptions, job companies, roles,
+/// descriptions and responsibilities, and skill names and descriptions. Hits of every
+/// kind are ranked together, best match first.
+#[utoipa::path(
+    get,
+    path = "/search",
+    responses(
+        (status = 200, description = "Ranked search hits", body = Vec<SearchHit>),
+        (status = 400, description = "Missing or empty query, or invalid limit", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        SearchParams
+    ),
+    tag = "search"
+)]
+pub async fn search(
+    State(pool): State<PgPool>,
+    ApiQuery(params): ApiQuery<SearchParams>,
+) -> Result<Json<Vec<SearchHit>>, ApiError> {
+    let limit = params
+        .validate()
+        .map_err(|detail| ApiError::InvalidRequest {
+            status: StatusCode::BAD_REQUEST,
+            detail,
+        })?;
+
+    let hits = search_db::search(&pool, params.q.trim(), limit).await?;
+    Ok(Json(hits))
+}
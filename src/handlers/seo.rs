@@ -0,0 +1,110 @@
+use crate::db::{jobs_db, projects_db, skills_db};
+use crate::error::{ApiError, ProblemDetails};
+use crate::extract::ApiPath;
+use crate::models::profile::Profile;
+use crate::seo::{CreativeWorkLd, PersonLd, SiteConfig, sitemap};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::{Json, extract::State};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+const JSON_LD: &str = "application/ld+json";
+
+/// schema.org Person
+///
+/// JSON-LD describing the portfolio's owner: the profile, current employers as
+/// `worksFor`, every job as `hasOccupation` and every skill as `knowsAbout`
+#[utoipa::path(
+    get,
+    path = "/seo/person.jsonld",
+    responses(
+        (status = 200, description = "schema.org Person", body = PersonLd, content_type = "application/ld+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "seo"
+)]
+pub async fn get_person_jsonld(
+    State(pool): State<PgPool>,
+    State(profile): State<Arc<Profile>>,
+    State(site): State<Arc<SiteConfig>>,
+) -> Result<Response, ApiError> {
+    let (jobs, skills) = tokio::try_join!(
+        jobs_db::fetch_all_jobs(&pool),
+        skills_db::fetch_all_skills(&pool),
+    )?;
+    let person = PersonLd::new(&site, &profile, &jobs, &skills);
+
+    Ok(([(header::CONTENT_TYPE, JSON_LD)], Json(person)).into_response())
+}
+
+/// schema.org CreativeWork for a project
+///
+/// JSON-LD describing a project, for embedding in the project's page on the public site
+#[utoipa::path(
+    get,
+    path = "/seo/projects/{project_id}.jsonld",
+    params(
+        ("project_id" = i32, Path, description = "ID of the project")
+    ),
+    responses(
+        (status = 200, description = "schema.org CreativeWork", body = CreativeWorkLd, content_type = "application/ld+json"),
+        (status = 400, description = "Invalid project ID", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Project not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "seo"
+)]
+pub async fn get_project_jsonld(
+    State(pool): State<PgPool>,
+    State(site): State<Arc<SiteConfig>>,
+    ApiPath(file_name): ApiPath<String>,
+) -> Result<Response, ApiError> {
+    // The router cannot match `{project_id}.jsonld`, so the whole segment is captured
+    let Some(project_id) = file_name.strip_suffix(".jsonld") else {
+        return Err(ApiError::NotFound(
+            "No route matches the requested path".to_string(),
+        ));
+    };
+    let project_id: i32 = project_id.parse().map_err(|_| ApiError::InvalidRequest {
+        status: StatusCode::BAD_REQUEST,
+        detail: format!("`{}` is not a valid project ID", project_id),
+    })?;
+
+    let activity = projects_db::fetch_project_activity_by_id(&pool, project_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Project not found".to_string()))?;
+    let work = CreativeWorkLd::new(&site, &activity);
+
+    Ok(([(header::CONTENT_TYPE, JSON_LD)], Json(work)).into_response())
+}
+
+/// Sitemap
+///
+/// Lists the public site's home page and the page of every project and job, under the
+/// configured `SITE_BASE_URL`
+#[utoipa::path(
+    get,
+    path = "/sitemap.xml",
+    responses(
+        (status = 200, description = "Sitemap", body = String, content_type = "application/xml"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "seo"
+)]
+pub async fn get_sitemap(
+    State(pool): State<PgPool>,
+    State(site): State<Arc<SiteConfig>>,
+) -> Result<Response, ApiError> {
+    let (projects, jobs) = tokio::try_join!(
+        projects_db::fetch_project_dates(&pool),
+        jobs_db::fetch_job_dates(&pool),
+    )?;
+    let xml = sitemap(&site, &projects, &jobs);
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        xml,
+    )
+        .into_response())
+}
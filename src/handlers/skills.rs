@@ -1,30 +1,73 @@
+use crate::db::lenient::Lenient;
 use crate::db::skills_db;
-use crate::models::skill::Skill;
-use axum::response::IntoResponse;
-use axum::{Json, extract::State, http::StatusCode};
+use crate::error::{ApiError, ProblemDetails};
+use crate::extract::{ApiJson, ApiPath, ApiQuery};
+use crate::handlers::decode::DecodeParams;
+use crate::handlers::page::{page_request, page_response};
+use crate::models::page::{Page, PageParams};
+use crate::models::skill::{
+    HierarchyParams, NewSkill, RelatedSkill, Skill, SkillFilters, SkillNode, SkillPatch,
+};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::{Json, extract::OriginalUri, extract::State, http::StatusCode};
+use serde::Deserialize;
 use sqlx::PgPool;
-use tracing::error;
+use utoipa::IntoParams;
+
+fn skill_not_found() -> ApiError {
+    ApiError::NotFound("Skill not found".to_string())
+}
+
+fn max_depth(params: &HierarchyParams) -> Result<i32, ApiError> {
+    params
+        .max_depth()
+        .map_err(|detail| ApiError::InvalidRequest {
+            status: StatusCode::BAD_REQUEST,
+            detail,
+        })
+}
 
 /// Get all skills
 ///
 /// Returns a list of all skills in the portfolio
+///
+/// Sortable by `name`, `proficiency` and `id`; defaults to `id`
 #[utoipa::path(
     get,
     path = "/skills",
     responses(
-        (status = 200, description = "List of skills retrieved successfully", body = Vec<Skill>),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "List of skills retrieved successfully", body = Page<Skill>, headers(("Link" = String, description = "`<...>; rel=\"next\"` link to the next page, when there is one"))),
+        (status = 400, description = "Invalid limit, sort or cursor", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        DecodeParams,
+        PageParams,
+        SkillFilters
     ),
     tag = "skills"
 )]
-pub async fn get_skills(State(pool): State<PgPool>) -> impl IntoResponse {
-    match skills_db::fetch_skills(&pool).await {
-        Ok(skills) => (StatusCode::OK, Json(skills)).into_response(),
-        Err(e) => {
-            error!("Failed to get skills: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch skills").into_response()
-        }
+pub async fn get_skills(
+    State(pool): State<PgPool>,
+    OriginalUri(uri): OriginalUri,
+    ApiQuery(decode): ApiQuery<DecodeParams>,
+    ApiQuery(page_params): ApiQuery<PageParams>,
+    ApiQuery(filters): ApiQuery<SkillFilters>,
+) -> Result<Response, ApiError> {
+    let page = page_request(
+        &page_params,
+        skills_db::SKILL_SORT_KEYS,
+        skills_db::SKILL_DEFAULT_SORT,
+    )?;
+
+    if decode.lenient {
+        let skills = skills_db::fetch_skills::<Lenient<Skill>>(&pool, &filters, &page).await?;
+        return Ok(page_response(&uri, skills));
     }
+
+    let skills = skills_db::fetch_skills::<Skill>(&pool, &filters, &page).await?;
+    Ok(page_response(&uri, skills))
 }
 
 /// Get a single skill by ID
@@ -35,26 +78,250 @@ pub async fn get_skills(State(pool): State<PgPool>) -> impl IntoResponse {
     path = "/skills/{skill_id}",
     responses(
         (status = 200, description = "Skill retrieved successfully", body = Skill),
-        (status = 404, description = "Skill not found"),
-        (status = 500, description = "Internal server error")
+        (status = 404, description = "Skill not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
     ),
     params(
-        ("skill_id" = i32, Path, description = "ID of the skill to retrieve")
+        ("skill_id" = i32, Path, description = "ID of the skill to retrieve"),
+        DecodeParams
     ),
     tag = "skills"
 )]
 pub async fn get_skill_by_id(
     State(pool): State<PgPool>,
-    axum::extract::Path(skill_id): axum::extract::Path<i32>,
-) -> impl IntoResponse {
-    match skills_db::fetch_skill_by_id(&pool, skill_id).await {
-        Ok(Some(skill)) => (StatusCode::OK, Json(skill)).into_response(),
-        Ok(None) => (StatusCode::NOT_FOUND, "Skill not found").into_response(),
-        Err(e) => {
-            error!("Failed to get skill: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch skill").into_response()
-        }
-    }
+    ApiPath(skill_id): ApiPath<i32>,
+    ApiQuery(decode): ApiQuery<DecodeParams>,
+) -> Result<Response, ApiError> {
+    let skill = if decode.lenient {
+        skills_db::fetch_skill_by_id::<Lenient<Skill>>(&pool, skill_id)
+            .await?
+            .map(|skill| Json(skill).into_response())
+    } else {
+        skills_db::fetch_skill_by_id::<Skill>(&pool, skill_id)
+            .await?
+            .map(|skill| Json(skill).into_response())
+    };
+
+    skill.ok_or_else(skill_not_found)
 }
 
+/// Query parameters accepted when deleting a skill
+#[derive(Deserialize, Debug, Default, IntoParams)]
+pub struct DeleteSkillParams {
+    /// Also delete all descendant skills and every project link to them
+    #[serde(default)]
+    pub cascade: bool,
+}
 
+/// Get the skill hierarchy
+///
+/// Returns every top-level skill with its sub-skills nested under `children`
+#[utoipa::path(
+    get,
+    path = "/skills/tree",
+    responses(
+        (status = 200, description = "Skill tree retrieved successfully", body = Vec<SkillNode>),
+        (status = 400, description = "Invalid max_depth", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        HierarchyParams
+    ),
+    tag = "skills"
+)]
+pub async fn get_skill_tree(
+    State(pool): State<PgPool>,
+    ApiQuery(params): ApiQuery<HierarchyParams>,
+) -> Result<Json<Vec<SkillNode>>, ApiError> {
+    let tree = skills_db::fetch_skill_tree(&pool, max_depth(&params)?).await?;
+    Ok(Json(tree))
+}
+
+/// Get the ancestors of a skill
+///
+/// Returns the skill's parent, grandparent and so on, nearest first
+#[utoipa::path(
+    get,
+    path = "/skills/{skill_id}/ancestors",
+    responses(
+        (status = 200, description = "Ancestors retrieved successfully", body = Vec<RelatedSkill>),
+        (status = 400, description = "Invalid max_depth", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Skill not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("skill_id" = i32, Path, description = "ID of the skill to start from"),
+        HierarchyParams
+    ),
+    tag = "skills"
+)]
+pub async fn get_skill_ancestors(
+    State(pool): State<PgPool>,
+    ApiPath(skill_id): ApiPath<i32>,
+    ApiQuery(params): ApiQuery<HierarchyParams>,
+) -> Result<Json<Vec<RelatedSkill>>, ApiError> {
+    skills_db::fetch_skill_ancestors(&pool, skill_id, max_depth(&params)?)
+        .await?
+        .map(Json)
+        .ok_or_else(skill_not_found)
+}
+
+/// Get the descendants of a skill
+///
+/// Returns the skill's children, grandchildren and so on, level by level
+#[utoipa::path(
+    get,
+    path = "/skills/{skill_id}/descendants",
+    responses(
+        (status = 200, description = "Descendants retrieved successfully", body = Vec<RelatedSkill>),
+        (status = 400, description = "Invalid max_depth", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Skill not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("skill_id" = i32, Path, description = "ID of the skill to start from"),
+        HierarchyParams
+    ),
+    tag = "skills"
+)]
+pub async fn get_skill_descendants(
+    State(pool): State<PgPool>,
+    ApiPath(skill_id): ApiPath<i32>,
+    ApiQuery(params): ApiQuery<HierarchyParams>,
+) -> Result<Json<Vec<RelatedSkill>>, ApiError> {
+    skills_db::fetch_skill_descendants(&pool, skill_id, max_depth(&params)?)
+        .await?
+        .map(Json)
+        .ok_or_else(skill_not_found)
+}
+
+/// Create a skill
+///
+/// Validates the payload and returns the created skill with a `Location` header
+#[utoipa::path(
+    post,
+    path = "/skills",
+    request_body = NewSkill,
+    responses(
+        (status = 201, description = "Skill created successfully", body = Skill),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload or unknown parent skill", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "skills",
+    security(("bearer_auth" = ["skills:write"]))
+)]
+pub async fn create_skill(
+    State(pool): State<PgPool>,
+    ApiJson(new_skill): ApiJson<NewSkill>,
+) -> Result<impl IntoResponse, ApiError> {
+    new_skill.validate().map_err(ApiError::Validation)?;
+
+    let skill = skills_db::create_skill(&pool, &new_skill).await?;
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, format!("/skills/{}", skill.id))],
+        Json(skill),
+    ))
+}
+
+/// Replace a skill
+///
+/// Overwrites every field of the skill; a `parent_id` that would create a cycle is rejected
+#[utoipa::path(
+    put,
+    path = "/skills/{skill_id}",
+    request_body = NewSkill,
+    responses(
+        (status = 200, description = "Skill updated successfully", body = Skill),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Skill not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload, unknown parent or parent cycle", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("skill_id" = i32, Path, description = "ID of the skill to replace")
+    ),
+    tag = "skills",
+    security(("bearer_auth" = ["skills:write"]))
+)]
+pub async fn update_skill(
+    State(pool): State<PgPool>,
+    ApiPath(skill_id): ApiPath<i32>,
+    ApiJson(skill): ApiJson<NewSkill>,
+) -> Result<Json<Skill>, ApiError> {
+    skill.validate().map_err(ApiError::Validation)?;
+
+    skills_db::update_skill(&pool, skill_id, &skill)
+        .await?
+        .map(Json)
+        .ok_or_else(skill_not_found)
+}
+
+/// Partially update a skill
+///
+/// Applies only the supplied fields; a `parent_id` that would create a cycle is rejected
+#[utoipa::path(
+    patch,
+    path = "/skills/{skill_id}",
+    request_body = SkillPatch,
+    responses(
+        (status = 200, description = "Skill updated successfully", body = Skill),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Skill not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid payload, unknown parent or parent cycle", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("skill_id" = i32, Path, description = "ID of the skill to update")
+    ),
+    tag = "skills",
+    security(("bearer_auth" = ["skills:write"]))
+)]
+pub async fn patch_skill(
+    State(pool): State<PgPool>,
+    ApiPath(skill_id): ApiPath<i32>,
+    ApiJson(patch): ApiJson<SkillPatch>,
+) -> Result<Json<Skill>, ApiError> {
+    skills_db::patch_skill(&pool, skill_id, patch)
+        .await?
+        .map(Json)
+        .ok_or_else(skill_not_found)
+}
+
+/// Delete a skill
+///
+/// Refuses to delete a skill that is linked to projects or has child skills unless
+/// `cascade=true` is passed, in which case its descendants and their project links go too
+#[utoipa::path(
+    delete,
+    path = "/skills/{skill_id}",
+    responses(
+        (status = 204, description = "Skill deleted successfully"),
+        (status = 401, description = "Missing, invalid or revoked API token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "API token lacks the required scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Skill not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Skill is still referenced by projects or child skills", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal server error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    params(
+        ("skill_id" = i32, Path, description = "ID of the skill to delete"),
+        DeleteSkillParams
+    ),
+    tag = "skills",
+    security(("bearer_auth" = ["skills:write"]))
+)]
+pub async fn delete_skill(
+    State(pool): State<PgPool>,
+    ApiPath(skill_id): ApiPath<i32>,
+    ApiQuery(params): ApiQuery<DeleteSkillParams>,
+) -> Result<StatusCode, ApiError> {
+    if skills_db::delete_skill(&pool, skill_id, params.cascade).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(skill_not_found())
+    }
+}
@@ -0,0 +1,184 @@
+use crate::db::connection::{applied_migration_version, expected_migration_version};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tracing::info;
+use utoipa::ToSchema;
+
+/// Outcome of a health check, or of a whole report
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+}
+
+/// One check of a health report
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    /// How long the check took, in milliseconds
+    pub duration_ms: f64,
+    /// What was found, e.g. why the check failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Result of a liveness or readiness probe: `pass` only when every check passes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HealthReport {
+    pub status: CheckStatus,
+    pub checks: Vec<Check>,
+}
+
+impl HealthReport {
+    fn new(checks: Vec<Check>) -> Self {
+        let status = if checks.iter().all(|check| check.status == CheckStatus::Pass) {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail
+        };
+        Self { status, checks }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.status == CheckStatus::Pass
+    }
+}
+
+/// Liveness and readiness of the server for the orchestrator's probes.
+///
+/// The process is live as long as it answers. It is ready while it is not shutting
+/// down and the database answers `SELECT 1` and has every embedded migration applied,
+/// each within the readiness timeout.
+#[derive(Clone)]
+pub struct Health {
+    shutting_down: Arc<AtomicBool>,
+    readiness_timeout: Duration,
+}
+
+impl Health {
+    pub fn new(readiness_timeout: Duration) -> Self {
+        Self {
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            readiness_timeout,
+        }
+    }
+
+    /// Fails readiness from now on, so the orchestrator stops routing requests here
+    /// while in-flight ones drain. Liveness keeps passing so the process is not killed.
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::Relaxed);
+    }
+
+    /// Shutdown future for `axum::serve(..).with_graceful_shutdown(..)`: once `signal`
+    /// fires, fails readiness and waits `drain` before resolving. The listener keeps
+    /// accepting connections during the drain, so the orchestrator's probes see
+    /// `/readyz` fail and it stops sending new requests before the server stops.
+    pub async fn graceful_shutdown(self, signal: impl Future<Output = ()>, drain: Duration) {
+        signal.await;
+        self.begin_shutdown();
+        info!(
+            "Readiness is failing; draining for {:?} before shutting down",
+            drain
+        );
+        tokio::time::sleep(drain).await;
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::Relaxed)
+    }
+
+    /// Checks nothing: answering at all shows the process is live.
+    pub fn liveness(&self) -> HealthReport {
+        HealthReport::new(Vec::new())
+    }
+
+    /// Runs the readiness checks against `pool` concurrently.
+    pub async fn readiness(&self, pool: &PgPool) -> HealthReport {
+        let shutting_down = self.is_shutting_down();
+        let shutdown = Check {
+            name: "shutdown",
+            status: if shutting_down {
+                CheckStatus::Fail
+            } else {
+                CheckStatus::Pass
+            },
+            duration_ms: 0.0,
+            detail: shutting_down.then(|| "Server is shutting down".to_string()),
+        };
+        let (database, migrations) = tokio::join!(
+            self.timed("database", async {
+                sqlx::query("SELECT 1")
+                    .execute(pool)
+                    .await
+                    .map(|_| None)
+                    .map_err(|e| format!("SELECT 1 failed: {}", e))
+            }),
+            self.timed("migrations", check_migrations(pool)),
+        );
+
+        HealthReport::new(vec![shutdown, database, migrations])
+    }
+
+    /// Runs `check`, failing it if it does not finish within the readiness timeout.
+    async fn timed(
+        &self,
+        name: &'static str,
+        check: impl Future<Output = Result<Option<String>, String>>,
+    ) -> Check {
+        let start = Instant::now();
+        let outcome = tokio::time::timeout(self.readiness_timeout, check)
+            .await
+            .unwrap_or_else(|_| {
+                Err(format!(
+                    "Timed out after {} ms",
+                    self.readiness_timeout.as_millis()
+                ))
+            });
+        let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        match outcome {
+            Ok(detail) => Check {
+                name,
+                status: CheckStatus::Pass,
+                duration_ms,
+                detail,
+            },
+            Err(detail) => Check {
+                name,
+                status: CheckStatus::Fail,
+                duration_ms,
+                detail: Some(detail),
+            },
+        }
+    }
+}
+
+/// Passes when the newest embedded migration is applied. A newer schema passes too, so
+/// instances of the previous release stay ready while a rollout migrates ahead of them.
+async fn check_migrations(pool: &PgPool) -> Result<Option<String>, String> {
+    let expected = expected_migration_version();
+    let applied = applied_migration_version(pool)
+        .await
+        .map_err(|e| format!("Failed to read the migration version: {}", e))?;
+
+    let detail = format!(
+        "Applied version {}, expected {}",
+        version_label(applied),
+        version_label(expected)
+    );
+    if applied >= expected {
+        Ok(Some(detail))
+    } else {
+        Err(detail)
+    }
+}
+
+fn version_label(version: Option<i64>) -> String {
+    version.map_or_else(|| "none".to_string(), |version| version.to_string())
+}
@@ -0,0 +1,236 @@
+use crate::db::versions_db::DataVersion;
+use crate::error::ApiError;
+use crate::response_cache::{CachedResponse, ResponseCache};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tracing::warn;
+
+/// Whether a GET was answered from the [`ResponseCache`], `HIT` or `MISS`
+pub const X_CACHE: HeaderName = HeaderName::from_static("x-cache");
+
+/// Tables every portfolio resource is read from
+pub const ALL_TABLES: &[&str] = &["jobs", "projects", "projects_skills", "skills"];
+
+/// Fingerprint of the build's sources mixed into every ETag. Every instance of a build
+/// issues the same ETags, while a redeploy that changes how responses are rendered
+/// never validates a representation cached before it.
+const BUILD_SALT: &str = env!("BUILD_FINGERPRINT");
+
+/// How the GET responses of one route group are validated and cached
+#[derive(Clone)]
+pub struct CachePolicy {
+    pub pool: PgPool,
+    pub cache: ResponseCache,
+    /// Tables the group's responses are built from
+    pub tables: &'static [&'static str],
+    /// `Cache-Control` sent with successful responses
+    pub cache_control: HeaderValue,
+}
+
+impl CachePolicy {
+    pub fn new(
+        pool: PgPool,
+        cache: ResponseCache,
+        tables: &'static [&'static str],
+        cache_control: &'static str,
+    ) -> Self {
+        Self {
+            pool,
+            cache,
+            tables,
+            cache_control: HeaderValue::from_static(cache_control),
+        }
+    }
+}
+
+/// Validators of the representation at one URI for one version of its tables
+struct Validators {
+    etag: String,
+    last_modified: DateTime<Utc>,
+}
+
+impl Validators {
+    /// Responses are a pure function of the URI and the tables they are read from, so the
+    /// ETag is a hash of both and can be checked before the handler runs.
+    fn new(version: &DataVersion, uri: &Uri) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(BUILD_SALT.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(version.versions.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(uri.to_string().as_bytes());
+        let digest = hasher.finalize();
+
+        Self {
+            etag: format!("\"{}\"", hex::encode(&digest[..16])),
+            last_modified: version.last_modified,
+        }
+    }
+
+    fn apply(&self, policy: &CachePolicy, headers: &mut HeaderMap) {
+        if let Ok(etag) = HeaderValue::from_str(&self.etag) {
+            headers.insert(header::ETAG, etag);
+        }
+        if let Ok(date) = HeaderValue::from_str(&http_date(self.last_modified)) {
+            headers.insert(header::LAST_MODIFIED, date);
+        }
+        headers
+            .entry(header::CACHE_CONTROL)
+            .or_insert_with(|| policy.cache_control.clone());
+    }
+}
+
+/// Parsed `If-None-Match` header
+enum IfNoneMatch {
+    Any,
+    Tags(Vec<String>),
+}
+
+impl IfNoneMatch {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let values: Vec<&str> = headers
+            .get_all(header::IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+
+        let tags: Vec<&str> = values
+            .iter()
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if tags.contains(&"*") {
+            return Some(IfNoneMatch::Any);
+        }
+
+        // GET preconditions use the weak comparison, which ignores the `W/` prefix
+        Some(IfNoneMatch::Tags(
+            tags.into_iter()
+                .map(|tag| tag.strip_prefix("W/").unwrap_or(tag).to_string())
+                .collect(),
+        ))
+    }
+}
+
+fn http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+fn if_modified_since(headers: &HeaderMap) -> Option<DateTime<Utc>> {
+    let value = headers.get(header::IF_MODIFIED_SINCE)?.to_str().ok()?;
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+fn not_modified(policy: &CachePolicy, validators: &Validators) -> Response {
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    validators.apply(policy, response.headers_mut());
+    response
+}
+
+/// Runs the handler, keeping a successful GET response in the cache.
+async fn run_and_store(policy: &CachePolicy, request: Request, next: Next) -> Response {
+    let key = request.uri().to_string();
+    let store = request.method() == Method::GET;
+    let generation = policy.cache.generation();
+
+    let response = next.run(request).await;
+    if !store || response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            return ApiError::Internal(format!("Failed to buffer response: {}", e)).into_response();
+        }
+    };
+    let cached = CachedResponse {
+        headers: parts.headers.clone(),
+        body: body.clone(),
+    };
+    policy
+        .cache
+        .store_response(key, policy.tables, generation, cached);
+    Response::from_parts(parts, body.into())
+}
+
+/// Middleware that adds `ETag`, `Last-Modified` and the group's `Cache-Control` to
+/// successful GET and HEAD responses, and answers `If-None-Match` and
+/// `If-Modified-Since` with `304 Not Modified`.
+///
+/// The validators come from the change counters of [`CachePolicy::tables`], so a
+/// request whose ETag still matches is answered without running the handler. Other
+/// successful responses are served from and kept in the [`ResponseCache`]; writes
+/// through the group drop its tables from the cache right away rather than waiting
+/// for the notification.
+pub async fn conditional_get(
+    State(policy): State<CachePolicy>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        let response = next.run(request).await;
+        if response.status().is_success() {
+            policy.cache.invalidate_tables(policy.tables);
+        }
+        return response;
+    }
+
+    let version = match policy.cache.data_version(&policy.pool, policy.tables).await {
+        Ok(version) => version,
+        Err(error) => {
+            warn!(
+                "Serving without validators, table versions are unavailable: {}",
+                error
+            );
+            return next.run(request).await;
+        }
+    };
+    let validators = Validators::new(&version, request.uri());
+
+    let if_none_match = IfNoneMatch::from_headers(request.headers());
+    if let Some(IfNoneMatch::Tags(tags)) = &if_none_match
+        && tags.contains(&validators.etag)
+    {
+        return not_modified(&policy, &validators);
+    }
+    // If-Modified-Since is only considered without If-None-Match
+    let modified_since = match if_none_match {
+        None => if_modified_since(request.headers()),
+        Some(_) => None,
+    };
+    let matches_any = matches!(if_none_match, Some(IfNoneMatch::Any));
+
+    let (mut response, cache_status) = match policy.cache.response(&request.uri().to_string()) {
+        Some(cached) => (cached.into_response(), "HIT"),
+        None => (run_and_store(&policy, request, next).await, "MISS"),
+    };
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    // HTTP dates have whole-second precision
+    let unmodified = modified_since
+        .is_some_and(|since| validators.last_modified.timestamp() <= since.timestamp());
+    if matches_any || unmodified {
+        return not_modified(&policy, &validators);
+    }
+
+    validators.apply(&policy, response.headers_mut());
+    response
+        .headers_mut()
+        .insert(X_CACHE, HeaderValue::from_static(cache_status));
+    response
+}
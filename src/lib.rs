@@ -1,5 +1,21 @@
 pub mod api_docs;
+pub mod auth;
+pub mod config;
 pub mod db;
+pub mod degraded;
+pub mod error;
+pub mod export;
+pub mod extract;
+pub mod feeds;
+pub mod graphql;
 pub mod handlers;
+pub mod health;
+pub mod http_cache;
+pub mod metrics;
 pub mod models;
+pub mod render;
+pub mod request_id;
+pub mod response_cache;
 pub mod routes;
+pub mod seo;
+pub mod state;
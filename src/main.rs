@@ -1,8 +1,9 @@
 use dotenv::dotenv;
+use portfolio_api::db;
+use portfolio_api::state::AppState;
 use std::net::SocketAddr;
 use tokio::signal;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use portfolio_api::db;
 #[tokio::main]
 async fn main() {
     // Load environment variables
@@ -17,17 +18,151 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // Initialize database connection
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Load the snapshot served while the database is down
+    let degraded = match portfolio_api::config::load_degraded_mode() {
+        Ok(degraded) => degraded,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Initialize database connection. Serving, unlike the one-off commands, can start
+    // from the snapshot and connect once the database is back.
     let pool = match db::connection::connect().await {
         Ok(pool) => pool,
+        Err(e) if args.is_empty() && degraded.has_snapshot() => {
+            tracing::warn!(
+                "Failed to connect to database, starting from the snapshot: {}",
+                e
+            );
+            let pool = match db::connection::connect_lazy() {
+                Ok(pool) => pool,
+                Err(e) => {
+                    tracing::error!("Failed to configure database: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            degraded.mark_unavailable();
+            if db::connection::migrations_enabled() {
+                let degraded = degraded.clone();
+                let pool = pool.clone();
+                tokio::spawn(async move {
+                    degraded.wait_until_available().await;
+                    if let Err(e) = db::connection::run_migrations(&pool).await {
+                        tracing::error!("Failed to apply migrations: {}", e);
+                    }
+                });
+            }
+            pool
+        }
         Err(e) => {
             tracing::error!("Failed to connect to database: {}", e);
             std::process::exit(1);
         }
     };
 
+    // `--migrate-only` applies the embedded migrations and exits, for deploy pipelines
+    if args.first().map(String::as_str) == Some("--migrate-only") {
+        if let Err(e) = db::connection::run_migrations(&pool).await {
+            tracing::error!("Failed to apply migrations: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // `--create-token <name> <scope>...` issues an API token for the write routes and exits
+    if args.first().map(String::as_str) == Some("--create-token") {
+        let Some((name, scopes)) = args[1..].split_first() else {
+            eprintln!("usage: portfolio-api --create-token <name> <scope>...");
+            std::process::exit(2);
+        };
+        match db::api_tokens_db::create_api_token(&pool, name, scopes).await {
+            Ok(token) => {
+                println!("{}", token);
+                return;
+            }
+            Err(e) => {
+                tracing::error!("Failed to create API token: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Load the résumé profile used by the export routes
+    let profile = match portfolio_api::config::load_profile() {
+        Ok(profile) => profile,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Load the HTML themes served under /render
+    let themes = match portfolio_api::config::load_themes() {
+        Ok(themes) => themes,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Load the public site URL the SEO routes point to
+    let site = match portfolio_api::config::load_site() {
+        Ok(site) => site,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Cache responses in memory, invalidated by table change notifications
+    let cache = match portfolio_api::config::load_cache() {
+        Ok(cache) => cache,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    cache.spawn_listener(pool.clone());
+
+    // Keep serving the snapshot while the database is down, and refresh it while it is up
+    let snapshot_interval = match portfolio_api::config::load_snapshot_interval() {
+        Ok(interval) => interval,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    degraded.spawn_reconnect(pool.clone());
+
+    // Answer the orchestrator's liveness and readiness probes
+    let health = match portfolio_api::config::load_health() {
+        Ok(health) => health,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let shutdown_drain = match portfolio_api::config::load_shutdown_drain() {
+        Ok(drain) => drain,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
     // Create the application router
-    let app = portfolio_api::routes::create_router(pool);
+    let state = AppState::new(pool, profile, themes, site)
+        .with_cache(cache)
+        .with_degraded_mode(degraded.clone())
+        .with_health(health.clone());
+    let app = portfolio_api::routes::create_router(state.clone());
+    if degraded.takes_snapshots() {
+        degraded.spawn_snapshots(app.clone(), state, snapshot_interval);
+    }
 
     // Get port from environment variable or use 8080 as default
     let port = std::env::var("PORT")
@@ -42,7 +177,7 @@ async fn main() {
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
 
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(health.graceful_shutdown(shutdown_signal(), shutdown_drain))
         .await
         .unwrap();
 }
@@ -0,0 +1,204 @@
+use axum::extract::{MatchedPath, Request};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::Response;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use sqlx::PgPool;
+use std::sync::LazyLock;
+use std::time::Instant;
+
+/// `route` label of requests that matched no route, so unknown paths cannot create
+/// unbounded label values
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Request extension that keeps a request out of the HTTP metrics, for requests the
+/// server sends itself, such as snapshot captures
+#[derive(Clone, Copy, Debug)]
+pub struct Untracked;
+
+/// Process-wide metrics, rendered by `/metrics`
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// Prometheus metrics of the HTTP server, the database queries and the connection pool
+///
+/// There is no histogram of the time spent waiting for a pool connection: sqlx checks
+/// connections out inside each query and exposes neither the wait nor the number of
+/// waiters. That wait is part of `db_query_duration_seconds`, and waits that exceed
+/// the acquire timeout are counted in `db_pool_acquire_timeouts_total`.
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    db_query_duration: HistogramVec,
+    db_pool_connections: IntGauge,
+    db_pool_idle_connections: IntGauge,
+    db_pool_max_connections: IntGauge,
+    db_pool_acquire_timeouts: IntCounter,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled"),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time to produce the response head of HTTP requests",
+            ),
+            &["method", "route"],
+        )
+        .expect("valid metric");
+        let db_query_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "db_query_duration_seconds",
+                "Duration of database functions, including waiting for a connection",
+            )
+            .buckets(vec![
+                0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+            ]),
+            &["query"],
+        )
+        .expect("valid metric");
+        let db_pool_connections =
+            IntGauge::new("db_pool_connections", "Open connections in the pool")
+                .expect("valid metric");
+        let db_pool_idle_connections =
+            IntGauge::new("db_pool_idle_connections", "Idle connections in the pool")
+                .expect("valid metric");
+        let db_pool_max_connections = IntGauge::new(
+            "db_pool_max_connections",
+            "Most connections the pool will open",
+        )
+        .expect("valid metric");
+        let db_pool_acquire_timeouts = IntCounter::new(
+            "db_pool_acquire_timeouts_total",
+            "Queries that gave up waiting for a pool connection",
+        )
+        .expect("valid metric");
+
+        for collector in [
+            Box::new(http_requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(db_query_duration.clone()),
+            Box::new(db_pool_connections.clone()),
+            Box::new(db_pool_idle_connections.clone()),
+            Box::new(db_pool_max_connections.clone()),
+            Box::new(db_pool_acquire_timeouts.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            db_query_duration,
+            db_pool_connections,
+            db_pool_idle_connections,
+            db_pool_max_connections,
+            db_pool_acquire_timeouts,
+        }
+    }
+
+    /// Counts a query that timed out waiting for a connection
+    pub fn record_pool_timeout(&self) {
+        self.db_pool_acquire_timeouts.inc();
+    }
+
+    /// Renders every metric in the Prometheus text format, with the pool gauges read
+    /// from `pool` now.
+    pub fn render(&self, pool: &PgPool) -> Result<String, prometheus::Error> {
+        let idle = pool.num_idle() as i64;
+        self.db_pool_connections.set(i64::from(pool.size()));
+        self.db_pool_idle_connections.set(idle);
+        self.db_pool_max_connections
+            .set(i64::from(pool.options().get_max_connections()));
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer).expect("the text format is UTF-8"))
+    }
+}
+
+/// Records the duration of a database function in `db_query_duration_seconds` when
+/// dropped, i.e. when the function returns or its future is cancelled.
+///
+/// ```ignore
+/// let _timer = QueryTimer::start("jobs_db::fetch_jobs");
+/// ```
+pub struct QueryTimer {
+    query: &'static str,
+    start: Instant,
+}
+
+impl QueryTimer {
+    pub fn start(query: &'static str) -> Self {
+        Self {
+            query,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        METRICS
+            .db_query_duration
+            .with_label_values(&[self.query])
+            .observe(self.start.elapsed().as_secs_f64());
+    }
+}
+
+/// `1xx` to `5xx`
+fn status_class(status: StatusCode) -> &'static str {
+    match status.as_u16() {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+/// Middleware that counts and times every request, labelled by method, matched route
+/// template (e.g. `/projects/{project_id}`) and status class. Requests carrying
+/// [`Untracked`] are passed through unrecorded.
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    if request.extensions().get::<Untracked>().is_some() {
+        return next.run(request).await;
+    }
+
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    METRICS
+        .http_request_duration
+        .with_label_values(&[method.as_str(), route.as_str()])
+        .observe(start.elapsed().as_secs_f64());
+    METRICS
+        .http_requests
+        .with_label_values(&[
+            method.as_str(),
+            route.as_str(),
+            status_class(response.status()),
+        ])
+        .inc();
+    response
+}
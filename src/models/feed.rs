@@ -0,0 +1,26 @@
+use crate::models::job::Job;
+use crate::models::project::Project;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Number of entries in each feed
+pub const FEED_ENTRY_LIMIT: i64 = 50;
+
+/// A project with the times it was created and last changed
+#[derive(Debug, Clone, FromRow)]
+pub struct ProjectActivity {
+    #[sqlx(flatten)]
+    pub project: Project,
+    pub created_at: DateTime<Utc>,
+    /// Also bumped when skills are linked or unlinked
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A job with the times it was created and last changed
+#[derive(Debug, Clone, FromRow)]
+pub struct JobActivity {
+    #[sqlx(flatten)]
+    pub job: Job,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,9 +1,10 @@
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
 
 /// Represents a job in the portfolio
-#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema, FromRow)]
 pub struct Job {
     pub id: i32,
     #[schema(example = "2023-01-01")]
@@ -17,3 +18,88 @@ pub struct Job {
     pub roles: String,
     pub responsibilities: String,
 }
+
+/// Payload for creating a job or fully replacing an existing one
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct NewJob {
+    #[schema(example = "2023-01-01")]
+    pub start_date: NaiveDate,
+    /// Must be omitted for the current job and set for every past job
+    #[serde(default)]
+    #[schema(example = "2024-01-01", nullable = true)]
+    pub end_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub is_current_job: bool,
+    pub company_name: String,
+    #[serde(default)]
+    pub company_website: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub roles: String,
+    #[serde(default)]
+    pub responsibilities: String,
+}
+
+impl NewJob {
+    /// Checks that the dates and the current-job flag are consistent with each other
+    pub fn validate(&self) -> Result<(), String> {
+        if self.company_name.trim().is_empty() {
+            return Err("company_name must not be empty".to_string());
+        }
+
+        match (self.is_current_job, self.end_date) {
+            (true, Some(_)) => Err("a current job must not have an end_date".to_string()),
+            (false, None) => Err("end_date is required unless is_current_job is true".to_string()),
+            (false, Some(end_date)) if end_date < self.start_date => {
+                Err("end_date must not be before start_date".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Payload for partially updating a job; omitted fields are left unchanged
+#[derive(Deserialize, Clone, Debug, Default, ToSchema)]
+pub struct JobPatch {
+    #[schema(example = "2023-01-01")]
+    pub start_date: Option<NaiveDate>,
+    /// Send `null` to clear the end date
+    #[serde(default, deserialize_with = "crate::models::nullable::deserialize")]
+    #[schema(value_type = Option<NaiveDate>, example = "2024-01-01", nullable = true)]
+    pub end_date: Option<Option<NaiveDate>>,
+    pub is_current_job: Option<bool>,
+    pub company_name: Option<String>,
+    pub company_website: Option<String>,
+    pub description: Option<String>,
+    pub roles: Option<String>,
+    pub responsibilities: Option<String>,
+}
+
+impl JobPatch {
+    /// Merges the patch over an existing job, producing the full replacement payload
+    pub fn apply_to(self, job: Job) -> NewJob {
+        NewJob {
+            start_date: self.start_date.unwrap_or(job.start_date),
+            end_date: self.end_date.unwrap_or(job.end_date),
+            is_current_job: self.is_current_job.unwrap_or(job.is_current_job),
+            company_name: self.company_name.unwrap_or(job.company_name),
+            company_website: self.company_website.unwrap_or(job.company_website),
+            description: self.description.unwrap_or(job.description),
+            roles: self.roles.unwrap_or(job.roles),
+            responsibilities: self.responsibilities.unwrap_or(job.responsibilities),
+        }
+    }
+}
+
+/// Query parameters filtering `/jobs`
+#[derive(Deserialize, Clone, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JobFilters {
+    /// Only the current job (`true`) or only past jobs (`false`)
+    pub current: Option<bool>,
+    /// Only jobs still held on or after this date, i.e. current jobs and jobs that
+    /// ended on or after it
+    #[param(value_type = Option<String>, format = Date, example = "2020-01-01")]
+    pub from: Option<NaiveDate>,
+}
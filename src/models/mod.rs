@@ -1,3 +1,9 @@
+pub mod feed;
 pub mod job;
+pub mod nullable;
+pub mod page;
+pub mod profile;
 pub mod project;
+pub mod search;
 pub mod skill;
+pub mod validation;
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes a nullable field of a partial-update payload.
+///
+/// Used together with `#[serde(default)]` so that an omitted field stays `None`
+/// (leave unchanged) while an explicit `null` becomes `Some(None)` (clear the value).
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,138 @@
+use crate::db::pagination::{Direction, PageRequest, SortKey, SortTerm};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Page size used when `limit` is omitted
+pub const DEFAULT_PAGE_LIMIT: u32 = 50;
+/// Largest accepted `limit`
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// Pagination and sorting parameters shared by every list endpoint
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageParams {
+    /// Maximum number of items to return, between 1 and 100 (default 50)
+    pub limit: Option<u32>,
+    /// Opaque `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Comma-separated sort fields, each optionally prefixed with `-` for descending
+    /// order, e.g. `-start_date,company_name`
+    pub sort: Option<String>,
+}
+
+/// One page of a listing
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor to pass as `?cursor=` for the next page; `null` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Decoded form of the opaque `cursor` parameter
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Cursor {
+    /// Canonical sort the cursor was issued for
+    sort: String,
+    /// Sort values of the last row of the page
+    after: Vec<String>,
+}
+
+impl Cursor {
+    pub fn new(sort: String, after: Vec<String>) -> Self {
+        Cursor { sort, after }
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("cursor serializes to JSON");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, String> {
+        URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| "cursor is malformed".to_string())
+    }
+}
+
+impl PageParams {
+    /// Validates the parameters against the sort keys a listing allows.
+    ///
+    /// `default_sort` is used when `sort` is omitted; an ascending `id` tie-breaker is
+    /// appended to every sort so that keyset pagination is stable.
+    pub fn resolve(
+        &self,
+        keys: &'static [SortKey],
+        default_sort: &str,
+    ) -> Result<PageRequest, String> {
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if limit == 0 || limit > MAX_PAGE_LIMIT {
+            return Err(format!("limit must be between 1 and {}", MAX_PAGE_LIMIT));
+        }
+
+        let sort = parse_sort(self.sort.as_deref().unwrap_or(default_sort), keys)?;
+        let mut page = PageRequest {
+            limit: i64::from(limit),
+            sort,
+            after: None,
+        };
+
+        if let Some(cursor) = &self.cursor {
+            let cursor = Cursor::decode(cursor)?;
+            if cursor.sort != page.sort_string() {
+                return Err("cursor was issued for a different sort".to_string());
+            }
+            let valid = cursor.after.len() == page.sort.len()
+                && page
+                    .sort
+                    .iter()
+                    .zip(&cursor.after)
+                    .all(|(term, value)| term.key.key_type.accepts(value));
+            if !valid {
+                return Err("cursor is malformed".to_string());
+            }
+            page.after = Some(cursor.after);
+        }
+
+        Ok(page)
+    }
+}
+
+fn parse_sort(sort: &str, keys: &'static [SortKey]) -> Result<Vec<SortTerm>, String> {
+    let mut terms: Vec<SortTerm> = Vec::new();
+
+    for field in sort.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        let (name, direction) = match field.strip_prefix('-') {
+            Some(name) => (name, Direction::Desc),
+            None => (field, Direction::Asc),
+        };
+        let key = keys.iter().find(|key| key.name == name).ok_or_else(|| {
+            let allowed: Vec<&str> = keys.iter().map(|key| key.name).collect();
+            format!(
+                "cannot sort by `{}`; expected one of: {}",
+                name,
+                allowed.join(", ")
+            )
+        })?;
+        if terms.iter().any(|term| term.key.name == key.name) {
+            return Err(format!("`{}` appears more than once in sort", name));
+        }
+        terms.push(SortTerm { key, direction });
+    }
+
+    if !terms.iter().any(|term| term.key.name == "id") {
+        let id = keys
+            .iter()
+            .find(|key| key.name == "id")
+            .expect("every listing can be sorted by id");
+        terms.push(SortTerm {
+            key: id,
+            direction: Direction::Asc,
+        });
+    }
+
+    Ok(terms)
+}
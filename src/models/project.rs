@@ -1,9 +1,11 @@
 use crate::models::skill::Skill;
+use crate::models::validation::validate_http_url;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
 
 /// Represents a portfolio project with its details and metadata
-#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema, FromRow)]
 pub struct Project {
     /// Unique identifier for the project
     pub id: i32,
@@ -16,5 +18,153 @@ pub struct Project {
     /// Optional job ID associated with the project
     pub job_id: Option<i32>,
     /// List of technologies used in the project
+    #[sqlx(json)]
     pub skills: Vec<Skill>,
 }
+
+/// Payload for creating a project or fully replacing an existing one
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct NewProject {
+    /// Title of the project
+    pub name: String,
+    /// Detailed description of the project
+    #[serde(default)]
+    pub description: String,
+    /// Optional GitHub repository URL for the project
+    #[serde(default)]
+    pub github_url: Option<String>,
+    /// Optional job ID associated with the project
+    #[serde(default)]
+    pub job_id: Option<i32>,
+    /// IDs of the skills to link to the project, replacing any existing links
+    #[serde(default)]
+    pub skill_ids: Vec<i32>,
+}
+
+impl NewProject {
+    /// Checks the payload fields that the database does not constrain
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+
+        if let Some(github_url) = &self.github_url {
+            validate_http_url("github_url", github_url)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Payload for partially updating a project; omitted fields are left unchanged
+#[derive(Deserialize, Clone, Debug, Default, ToSchema)]
+pub struct ProjectPatch {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Send `null` to remove the GitHub URL
+    #[serde(default, deserialize_with = "crate::models::nullable::deserialize")]
+    #[schema(value_type = Option<String>, nullable = true)]
+    pub github_url: Option<Option<String>>,
+    /// Send `null` to detach the project from its job
+    #[serde(default, deserialize_with = "crate::models::nullable::deserialize")]
+    #[schema(value_type = Option<i32>, nullable = true)]
+    pub job_id: Option<Option<i32>>,
+    /// When present, replaces the linked skill set
+    pub skill_ids: Option<Vec<i32>>,
+}
+
+impl ProjectPatch {
+    /// Checks the supplied fields like [`NewProject::validate`]; omitted fields keep
+    /// values that were checked when they were written
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.as_ref().is_some_and(|name| name.trim().is_empty()) {
+            return Err("name must not be empty".to_string());
+        }
+
+        if let Some(Some(github_url)) = &self.github_url {
+            validate_http_url("github_url", github_url)?;
+        }
+
+        Ok(())
+    }
+
+    /// Merges the patch over an existing project, producing the full replacement payload
+    pub fn apply_to(self, project: Project) -> NewProject {
+        NewProject {
+            name: self.name.unwrap_or(project.name),
+            description: self.description.unwrap_or(project.description),
+            github_url: self.github_url.unwrap_or(project.github_url),
+            job_id: self.job_id.unwrap_or(project.job_id),
+            skill_ids: self
+                .skill_ids
+                .unwrap_or_else(|| project.skills.iter().map(|skill| skill.id).collect()),
+        }
+    }
+}
+
+/// Payload for replacing the full set of skills linked to a project
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct ProjectSkillIds {
+    pub skill_ids: Vec<i32>,
+}
+
+/// How `skills` in [`ProjectFilters`] is matched
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillMatch {
+    /// Projects using at least one of the skills
+    #[default]
+    Any,
+    /// Projects using every one of the skills
+    All,
+}
+
+/// Query parameters filtering `/projects`
+#[derive(Deserialize, Clone, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectFilters {
+    /// Comma-separated skill names, matched case-insensitively, e.g. `rust,postgres`
+    pub skills: Option<String>,
+    /// Whether projects must use `any` (default) or `all` of `skills`
+    #[serde(rename = "match", default)]
+    #[param(inline)]
+    pub skill_match: SkillMatch,
+    /// Only projects with (`true`) or without (`false`) a GitHub URL
+    pub has_github: Option<bool>,
+}
+
+impl ProjectFilters {
+    /// The distinct, lower-cased skill names in `skills`
+    pub fn skill_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .skills
+            .iter()
+            .flat_map(|skills| skills.split(','))
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// A project listed by skill, with the skills that caused it to match
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema, FromRow)]
+pub struct MatchedProject {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub project: Project,
+    /// IDs of the project's skills that matched: the requested skill and, with
+    /// `include_descendants`, any of its sub-skills
+    pub matched_skill_ids: Vec<i32>,
+}
+
+/// Query parameters for listing projects by skill
+#[derive(Deserialize, Clone, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SkillMatchParams {
+    /// Also match projects using any sub-skill of the skill, e.g. React for JavaScript
+    #[serde(default)]
+    pub include_descendants: bool,
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+
+/// Number of hits returned when `limit` is omitted
+pub const DEFAULT_SEARCH_LIMIT: u32 = 20;
+/// Largest accepted `limit`
+pub const MAX_SEARCH_LIMIT: u32 = 100;
+
+/// The kind of record a search hit points to
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, ToSchema, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum SearchKind {
+    Project,
+    Job,
+    Skill,
+}
+
+/// A ranked full-text search hit
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema, FromRow)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    /// ID of the project, job or skill
+    pub id: i32,
+    /// Relevance; higher is better
+    pub score: f32,
+    /// HTML snippet of the matched text: the text is HTML-escaped and matches are
+    /// wrapped in `<mark>` tags
+    pub highlight: String,
+}
+
+/// Query parameters for `/search`
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchParams {
+    /// Search terms; supports `"quoted phrases"`, `or` and `-excluded` words
+    pub q: String,
+    /// Maximum number of hits to return, between 1 and 100 (default 20)
+    pub limit: Option<u32>,
+}
+
+impl SearchParams {
+    /// Checks the query is non-empty and the limit in range, returning the limit
+    pub fn validate(&self) -> Result<i64, String> {
+        if self.q.trim().is_empty() {
+            return Err("q must not be empty".to_string());
+        }
+
+        let limit = self.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+        if limit == 0 || limit > MAX_SEARCH_LIMIT {
+            return Err(format!("limit must be between 1 and {}", MAX_SEARCH_LIMIT));
+        }
+        Ok(i64::from(limit))
+    }
+}
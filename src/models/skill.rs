@@ -1,9 +1,11 @@
 use crate::db::proficiency_enum::Proficiency;
+use crate::models::validation::validate_http_url;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
 
 /// Represents a technology/tool used in projects
-#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema, FromRow)]
 pub struct Skill {
     /// Unique identifier for the technology
     pub id: i32,
@@ -17,3 +19,115 @@ pub struct Skill {
     pub proficiency: Proficiency,
     pub parent_id: Option<i32>,
 }
+
+/// Payload for creating a skill or fully replacing an existing one
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct NewSkill {
+    /// Name of the technology
+    pub name: String,
+    /// Description of the technology
+    #[serde(default)]
+    pub description: String,
+    /// Official website URL for the technology
+    pub official_site_url: String,
+    /// Proficiency level in the technology
+    pub proficiency: Proficiency,
+    /// Optional parent skill; must not be the skill itself or one of its descendants
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+}
+
+impl NewSkill {
+    /// Checks the payload fields that the database does not constrain
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+
+        validate_http_url("official_site_url", &self.official_site_url)
+    }
+}
+
+/// Payload for partially updating a skill; omitted fields are left unchanged
+#[derive(Deserialize, Clone, Debug, Default, ToSchema)]
+pub struct SkillPatch {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub official_site_url: Option<String>,
+    pub proficiency: Option<Proficiency>,
+    /// Send `null` to turn the skill into a top-level skill
+    #[serde(default, deserialize_with = "crate::models::nullable::deserialize")]
+    #[schema(value_type = Option<i32>, nullable = true)]
+    pub parent_id: Option<Option<i32>>,
+}
+
+impl SkillPatch {
+    /// Merges the patch over an existing skill, producing the full replacement payload
+    pub fn apply_to(self, skill: Skill) -> NewSkill {
+        NewSkill {
+            name: self.name.unwrap_or(skill.name),
+            description: self.description.unwrap_or(skill.description),
+            official_site_url: self.official_site_url.unwrap_or(skill.official_site_url),
+            proficiency: self.proficiency.unwrap_or(skill.proficiency),
+            parent_id: self.parent_id.unwrap_or(skill.parent_id),
+        }
+    }
+}
+
+/// Query parameters filtering `/skills`
+#[derive(Deserialize, Clone, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SkillFilters {
+    /// Only skills at this proficiency level or above
+    #[param(inline)]
+    pub proficiency_min: Option<Proficiency>,
+    /// Only direct children of this skill
+    pub parent_id: Option<i32>,
+}
+
+/// Levels walked by the hierarchy endpoints when `max_depth` is omitted
+pub const DEFAULT_HIERARCHY_DEPTH: u32 = 16;
+/// Largest accepted `max_depth`
+pub const MAX_HIERARCHY_DEPTH: u32 = 64;
+
+/// A skill with its sub-skills, as returned by `/skills/tree`
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct SkillNode {
+    #[serde(flatten)]
+    pub skill: Skill,
+    /// Skills whose `parent_id` is this skill, ordered by name
+    #[schema(no_recursion)]
+    pub children: Vec<SkillNode>,
+}
+
+/// A skill found by walking the hierarchy from another skill
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema, FromRow)]
+pub struct RelatedSkill {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub skill: Skill,
+    /// Number of `parent_id` links between this skill and the starting skill
+    pub depth: i32,
+}
+
+/// Query parameters bounding a walk of the skill hierarchy
+#[derive(Deserialize, Clone, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HierarchyParams {
+    /// Maximum number of levels to walk, between 1 and 64 (default 16)
+    pub max_depth: Option<u32>,
+}
+
+impl HierarchyParams {
+    /// Checks `max_depth` is in range, returning it
+    pub fn max_depth(&self) -> Result<i32, String> {
+        let max_depth = self.max_depth.unwrap_or(DEFAULT_HIERARCHY_DEPTH);
+        if max_depth == 0 || max_depth > MAX_HIERARCHY_DEPTH {
+            return Err(format!(
+                "max_depth must be between 1 and {}",
+                MAX_HIERARCHY_DEPTH
+            ));
+        }
+        Ok(max_depth as i32)
+    }
+}
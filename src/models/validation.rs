@@ -0,0 +1,11 @@
+use url::Url;
+
+/// Checks that `value` is an absolute `http` or `https` URL.
+///
+/// `field` is used to build the error message returned to the client.
+pub fn validate_http_url(field: &str, value: &str) -> Result<(), String> {
+    match Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(()),
+        _ => Err(format!("{} must be an absolute http(s) URL", field)),
+    }
+}
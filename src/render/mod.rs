@@ -0,0 +1,162 @@
+use crate::export::context::ResumeContext;
+use minijinja::{Environment, Value, context};
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Template every theme renders its page from
+const ENTRY_TEMPLATE: &str = "index.html";
+
+/// Themes compiled into the binary, as (name, entry template) pairs
+const BUILTIN_THEMES: [(&str, &str); 2] = [
+    ("classic", include_str!("themes/classic/index.html")),
+    ("minimal", include_str!("themes/minimal/index.html")),
+];
+
+/// The HTML themes pages can be rendered with.
+///
+/// A theme is a set of templates named `<theme>/<file>`, rendered from its
+/// `index.html`. Templates can include or extend their siblings by relative name,
+/// e.g. `{% extends "./base.html" %}`.
+#[derive(Debug)]
+pub struct Themes {
+    env: Environment<'static>,
+    names: BTreeSet<String>,
+}
+
+impl Themes {
+    /// The themes compiled into the binary
+    pub fn builtin() -> Self {
+        let mut env = Environment::new();
+        env.set_path_join_callback(join_relative);
+
+        let mut names = BTreeSet::new();
+        for (name, source) in BUILTIN_THEMES {
+            env.add_template_owned(format!("{}/{}", name, ENTRY_TEMPLATE), source)
+                .expect("built-in theme templates are valid");
+            names.insert(name.to_string());
+        }
+
+        Self { env, names }
+    }
+
+    /// Adds every theme directory found in `dir`.
+    ///
+    /// Each subdirectory holding an `index.html` is a theme named after the directory,
+    /// and every file in it becomes one of its templates. A theme with the name of a
+    /// built-in theme replaces it.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<(), String> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read themes directory {}: {}", dir.display(), e))?;
+
+        for entry in entries {
+            let theme_dir = entry
+                .map_err(|e| format!("Failed to read themes directory {}: {}", dir.display(), e))?
+                .path();
+            if !theme_dir.is_dir() {
+                continue;
+            }
+            let Some(name) = theme_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .filter(|name| is_valid_name(name))
+            else {
+                warn!(
+                    "Skipping theme directory {}: theme names may only contain lowercase letters, digits, `-` and `_`",
+                    theme_dir.display()
+                );
+                continue;
+            };
+            if !theme_dir.join(ENTRY_TEMPLATE).is_file() {
+                warn!(
+                    "Skipping theme directory {}: it has no {}",
+                    theme_dir.display(),
+                    ENTRY_TEMPLATE
+                );
+                continue;
+            }
+
+            self.load_theme(name, &theme_dir)?;
+        }
+
+        Ok(())
+    }
+
+    fn load_theme(&mut self, name: &str, theme_dir: &Path) -> Result<(), String> {
+        let prefix = format!("{}/", name);
+        let stale: Vec<String> = self
+            .env
+            .templates()
+            .map(|(template, _)| template.to_string())
+            .filter(|template| template.starts_with(&prefix))
+            .collect();
+        for template in stale {
+            self.env.remove_template(&template);
+        }
+
+        let files = std::fs::read_dir(theme_dir)
+            .map_err(|e| format!("Failed to read theme {}: {}", theme_dir.display(), e))?;
+        for file in files {
+            let path = file
+                .map_err(|e| format!("Failed to read theme {}: {}", theme_dir.display(), e))?
+                .path();
+            let Some(file_name) = path.file_name().and_then(|file_name| file_name.to_str()) else {
+                continue;
+            };
+            if !path.is_file() {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read template {}: {}", path.display(), e))?;
+            self.env
+                .add_template_owned(format!("{}{}", prefix, file_name), source)
+                .map_err(|e| format!("Invalid template {}: {:#}", path.display(), e))?;
+        }
+
+        if !self.names.insert(name.to_string()) {
+            info!(
+                "Theme {} from {} replaces the built-in theme",
+                name,
+                theme_dir.display()
+            );
+        } else {
+            info!("Loaded theme {} from {}", name, theme_dir.display());
+        }
+        Ok(())
+    }
+
+    /// Names of the available themes, in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    pub fn contains(&self, theme: &str) -> bool {
+        self.names.contains(theme)
+    }
+
+    /// Renders a page with `theme`, which must exist.
+    ///
+    /// Besides the résumé context, templates get the theme's name as `theme`.
+    pub fn render(&self, theme: &str, resume: &ResumeContext) -> Result<String, minijinja::Error> {
+        let template = self
+            .env
+            .get_template(&format!("{}/{}", theme, ENTRY_TEMPLATE))?;
+        template.render(context! { theme => theme, ..Value::from_serialize(resume) })
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Resolves template names starting with `./` against the including template's theme
+fn join_relative<'s>(name: &'s str, parent: &'s str) -> Cow<'s, str> {
+    match (name.strip_prefix("./"), parent.split_once('/')) {
+        (Some(file_name), Some((theme, _))) => format!("{}/{}", theme, file_name).into(),
+        _ => name.into(),
+    }
+}
@@ -0,0 +1,287 @@
+use crate::db::versions_db::{DataVersion, fetch_data_version};
+use axum::body::{Body, Bytes};
+use axum::http::HeaderMap;
+use axum::response::Response;
+use serde::Serialize;
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+/// Channel the `bump_table_version` trigger notifies with the name of the changed table
+pub const NOTIFY_CHANNEL: &str = "table_changed";
+
+/// Most responses kept at once; the oldest is evicted to make room
+const MAX_ENTRIES: usize = 1024;
+
+/// Delays between attempts to re-subscribe after the notification connection drops
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// A successful response kept in memory, serialized
+#[derive(Clone)]
+pub struct CachedResponse {
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl CachedResponse {
+    pub fn into_response(self) -> Response {
+        let mut response = Response::new(Body::from(self.body));
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+/// Counters of `/cache/stats`
+#[derive(Serialize, Debug, ToSchema)]
+pub struct CacheStats {
+    /// GET requests answered from memory
+    pub hits: u64,
+    /// GET requests that had to run the handler
+    pub misses: u64,
+    /// Responses currently kept
+    pub entries: usize,
+    /// Whether invalidations arrive via `LISTEN/NOTIFY`; when `false`, entries expire
+    /// after `ttl_seconds`
+    pub listening: bool,
+    pub ttl_seconds: u64,
+}
+
+struct Cached<T> {
+    value: T,
+    /// Tables the value was read from
+    tables: &'static [&'static str],
+    stored_at: Instant,
+}
+
+#[derive(Default)]
+struct Store {
+    /// Bumped on every invalidation, so values read before it are not stored after it
+    generation: u64,
+    responses: HashMap<String, Cached<CachedResponse>>,
+    versions: HashMap<&'static [&'static str], Cached<DataVersion>>,
+}
+
+struct Inner {
+    ttl: Duration,
+    store: Mutex<Store>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    listening: AtomicBool,
+}
+
+/// In-memory cache of serialized GET responses and table versions.
+///
+/// Entries are dropped as soon as a table they were read from changes, as announced
+/// on [`NOTIFY_CHANNEL`] to the task started by [`ResponseCache::spawn_listener`].
+/// While that task is not subscribed, entries expire after the TTL instead.
+#[derive(Clone)]
+pub struct ResponseCache {
+    inner: Arc<Inner>,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                ttl,
+                store: Mutex::new(Store::default()),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                listening: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    fn store(&self) -> std::sync::MutexGuard<'_, Store> {
+        self.inner
+            .store
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn is_fresh<T>(&self, cached: &Cached<T>) -> bool {
+        self.inner.listening.load(Ordering::Relaxed) || cached.stored_at.elapsed() < self.inner.ttl
+    }
+
+    /// Current invalidation generation, to pass to [`ResponseCache::store_response`]
+    pub fn generation(&self) -> u64 {
+        self.store().generation
+    }
+
+    /// Looks up the response stored under `key`, counting a hit or a miss.
+    pub fn response(&self, key: &str) -> Option<CachedResponse> {
+        let found = self
+            .store()
+            .responses
+            .get(key)
+            .filter(|cached| self.is_fresh(cached))
+            .map(|cached| cached.value.clone());
+
+        let counter = match found {
+            Some(_) => &self.inner.hits,
+            None => &self.inner.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    /// Stores a response read from `tables`, unless they changed since `generation`.
+    pub fn store_response(
+        &self,
+        key: String,
+        tables: &'static [&'static str],
+        generation: u64,
+        response: CachedResponse,
+    ) {
+        let mut store = self.store();
+        if store.generation != generation {
+            return;
+        }
+
+        if store.responses.len() >= MAX_ENTRIES && !store.responses.contains_key(&key) {
+            let oldest = store
+                .responses
+                .iter()
+                .min_by_key(|(_, cached)| cached.stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                store.responses.remove(&oldest);
+            }
+        }
+
+        store.responses.insert(
+            key,
+            Cached {
+                value: response,
+                tables,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Change counters of `tables`, read from the database only when not cached.
+    pub async fn data_version(
+        &self,
+        pool: &PgPool,
+        tables: &'static [&'static str],
+    ) -> Result<DataVersion, sqlx::Error> {
+        let generation = {
+            let store = self.store();
+            if let Some(cached) = store.versions.get(tables).filter(|c| self.is_fresh(c)) {
+                return Ok(cached.value.clone());
+            }
+            store.generation
+        };
+
+        let version = fetch_data_version(pool, tables).await?;
+
+        let mut store = self.store();
+        if store.generation == generation {
+            store.versions.insert(
+                tables,
+                Cached {
+                    value: version.clone(),
+                    tables,
+                    stored_at: Instant::now(),
+                },
+            );
+        }
+        Ok(version)
+    }
+
+    /// Drops everything read from `table`.
+    pub fn invalidate(&self, table: &str) {
+        let mut store = self.store();
+        store.generation += 1;
+        store
+            .responses
+            .retain(|_, cached| !cached.tables.contains(&table));
+        store
+            .versions
+            .retain(|_, cached| !cached.tables.contains(&table));
+    }
+
+    /// Drops everything read from any of `tables`.
+    pub fn invalidate_tables(&self, tables: &[&str]) {
+        for table in tables {
+            self.invalidate(table);
+        }
+    }
+
+    /// Drops every entry.
+    pub fn clear(&self) {
+        let mut store = self.store();
+        store.generation += 1;
+        store.responses.clear();
+        store.versions.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            entries: self.store().responses.len(),
+            listening: self.inner.listening.load(Ordering::Relaxed),
+            ttl_seconds: self.inner.ttl.as_secs(),
+        }
+    }
+
+    /// Starts a task that subscribes to [`NOTIFY_CHANNEL`] on its own connection and
+    /// invalidates the cache on every notification, re-subscribing with exponential
+    /// backoff whenever the connection drops.
+    pub fn spawn_listener(&self, pool: PgPool) -> JoinHandle<()> {
+        let cache = self.clone();
+        tokio::spawn(async move { cache.listen(pool).await })
+    }
+
+    async fn listen(self, pool: PgPool) {
+        let mut delay = MIN_RECONNECT_DELAY;
+        loop {
+            match subscribe(&pool).await {
+                Ok(mut listener) => {
+                    // Changes made while unsubscribed were never announced
+                    self.inner.listening.store(true, Ordering::Relaxed);
+                    self.clear();
+                    delay = MIN_RECONNECT_DELAY;
+                    info!("Listening for table changes on `{}`", NOTIFY_CHANNEL);
+
+                    loop {
+                        match listener.try_recv().await {
+                            Ok(Some(notification)) => self.invalidate(notification.payload()),
+                            Ok(None) => {
+                                warn!("Notification connection lost");
+                                break;
+                            }
+                            Err(e) => {
+                                warn!("Notification connection failed: {}", e);
+                                break;
+                            }
+                        }
+                    }
+                    self.inner.listening.store(false, Ordering::Relaxed);
+                }
+                Err(e) => warn!("Failed to listen for table changes: {}", e),
+            }
+
+            info!(
+                "Cache entries expire after {:?} until notifications resume; retrying in {:?}",
+                self.inner.ttl, delay
+            );
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+}
+
+async fn subscribe(pool: &PgPool) -> Result<PgListener, sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(NOTIFY_CHANNEL).await?;
+    Ok(listener)
+}
@@ -1,35 +1,170 @@
 use crate::api_docs::ApiDoc;
-use crate::handlers::jobs::{get_job_by_id, get_jobs};
+use crate::auth::{JOBS_WRITE, PROJECTS_WRITE, RequiredScope, SKILLS_WRITE, require_write_scope};
+use crate::degraded::serve_stale;
+use crate::error::ApiError;
+use crate::handlers::cache::get_cache_stats;
+use crate::handlers::export::{
+    get_json_resume, get_resume_markdown, get_resume_pdf, get_resume_text,
+};
+use crate::handlers::feeds::{get_activity_feed, get_projects_feed};
+use crate::handlers::graphql::{graphiql, graphql};
+use crate::handlers::health::{get_healthz, get_readyz};
+use crate::handlers::jobs::{
+    create_job, delete_job, get_job_by_id, get_jobs, patch_job, update_job,
+};
+use crate::handlers::metrics::get_metrics;
 use crate::handlers::projects::{
-    get_project_by_id, get_projects, get_projects_by_job, get_projects_by_skill,
+    add_project_skill, create_project, delete_project, get_project_by_id, get_projects,
+    get_projects_by_job, get_projects_by_skill, patch_project, remove_project_skill,
+    replace_project_skills, update_project,
+};
+use crate::handlers::render::render_page;
+use crate::handlers::search::search;
+use crate::handlers::seo::{get_person_jsonld, get_project_jsonld, get_sitemap};
+use crate::handlers::skills::{
+    create_skill, delete_skill, get_skill_ancestors, get_skill_by_id, get_skill_descendants,
+    get_skill_tree, get_skills, patch_skill, update_skill,
+};
+use crate::http_cache::{ALL_TABLES, CachePolicy, conditional_get};
+use crate::metrics::track_requests;
+use crate::request_id::request_id;
+use crate::state::AppState;
+use axum::http::{HeaderValue, StatusCode};
+use axum::middleware;
+use axum::{
+    Router,
+    routing::{get, post, put},
 };
-use crate::handlers::skills::{get_skill_by_id, get_skills};
-use axum::http::HeaderValue;
-use axum::{Router, routing::get};
-use sqlx::PgPool;
 use tower_http::cors::{Any, CorsLayer};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::{Config, SwaggerUi};
 
 /// Creates and configures all API routes
-pub fn create_router(pool: PgPool) -> Router {
+///
+/// Mutating routes require an API token with the `<resource>:write` scope. GET routes
+/// are served with validators and the `Cache-Control` of their route group, and from
+/// the snapshot while the database is down.
+pub fn create_router(state: AppState) -> Router {
     // Create the base router
     let app = Router::new();
 
+    // Mutating routes need a token with the resource's write scope. The layer is
+    // applied per method router so unknown methods still get 405, not 401.
+    let projects_auth = middleware::from_fn_with_state(
+        RequiredScope::new(state.pool.clone(), PROJECTS_WRITE),
+        require_write_scope,
+    );
+    let jobs_auth = middleware::from_fn_with_state(
+        RequiredScope::new(state.pool.clone(), JOBS_WRITE),
+        require_write_scope,
+    );
+    let skills_auth = middleware::from_fn_with_state(
+        RequiredScope::new(state.pool.clone(), SKILLS_WRITE),
+        require_write_scope,
+    );
+
+    // Conditional GET per route group: the tables its responses are read from and how
+    // long shared caches may reuse them without revalidating
+    let cache = |tables: &'static [&'static str], cache_control: &'static str| {
+        middleware::from_fn_with_state(
+            CachePolicy::new(state.pool.clone(), state.cache.clone(), tables, cache_control),
+            conditional_get,
+        )
+    };
+
     // Create nested routers for each resource type
     let projects_router = Router::new()
-        .route("/", get(get_projects))
-        .route("/{project_id}", get(get_project_by_id))
+        .route(
+            "/",
+            get(get_projects)
+                .post(create_project)
+                .route_layer(projects_auth.clone()),
+        )
+        .route(
+            "/{project_id}",
+            get(get_project_by_id)
+                .put(update_project)
+                .patch(patch_project)
+                .delete(delete_project)
+                .route_layer(projects_auth.clone()),
+        )
+        .route(
+            "/{project_id}/skills",
+            put(replace_project_skills).route_layer(projects_auth.clone()),
+        )
+        .route(
+            "/{project_id}/skills/{skill_id}",
+            post(add_project_skill)
+                .delete(remove_project_skill)
+                .route_layer(projects_auth),
+        )
         .route("/job/{job_id}", get(get_projects_by_job))
-        .route("/skill/{skill_id}", get(get_projects_by_skill));
+        .route("/skill/{skill_id}", get(get_projects_by_skill))
+        .layer(cache(ALL_TABLES, "public, max-age=60"));
 
     let jobs_router = Router::new()
-        .route("/", get(get_jobs))
-        .route("/{job_id}", get(get_job_by_id));
+        .route(
+            "/",
+            get(get_jobs)
+                .post(create_job)
+                .route_layer(jobs_auth.clone()),
+        )
+        .route(
+            "/{job_id}",
+            get(get_job_by_id)
+                .put(update_job)
+                .patch(patch_job)
+                .delete(delete_job)
+                .route_layer(jobs_auth),
+        )
+        .layer(cache(&["jobs"], "public, max-age=60"));
 
     let skills_router = Router::new()
-        .route("/", get(get_skills))
-        .route("/{skill_id}", get(get_skill_by_id));
+        .route(
+            "/",
+            get(get_skills)
+                .post(create_skill)
+                .route_layer(skills_auth.clone()),
+        )
+        .route(
+            "/{skill_id}",
+            get(get_skill_by_id)
+                .put(update_skill)
+                .patch(patch_skill)
+                .delete(delete_skill)
+                .route_layer(skills_auth),
+        )
+        .route("/tree", get(get_skill_tree))
+        .route("/{skill_id}/ancestors", get(get_skill_ancestors))
+        .route("/{skill_id}/descendants", get(get_skill_descendants))
+        .layer(cache(&["skills"], "public, max-age=60"));
+
+    let export_router = Router::new()
+        .route("/json-resume", get(get_json_resume))
+        .route("/resume.pdf", get(get_resume_pdf))
+        .route("/resume.md", get(get_resume_markdown))
+        .route("/resume.txt", get(get_resume_text))
+        .layer(cache(ALL_TABLES, "public, max-age=300"));
+
+    let feeds_router = Router::new()
+        .route("/projects.atom", get(get_projects_feed))
+        .route("/activity.rss", get(get_activity_feed))
+        .layer(cache(ALL_TABLES, "public, max-age=900"));
+
+    let seo_router = Router::new()
+        .route("/person.jsonld", get(get_person_jsonld))
+        .route("/projects/{file_name}", get(get_project_jsonld))
+        .layer(cache(ALL_TABLES, "public, max-age=3600"));
+
+    // Top-level routes, merged rather than nested
+    let sitemap_router = Router::new()
+        .route("/sitemap.xml", get(get_sitemap))
+        .layer(cache(ALL_TABLES, "public, max-age=3600"));
+
+    let pages_router = Router::new()
+        .route("/search", get(search))
+        .route("/render/{theme}", get(render_page))
+        .layer(cache(ALL_TABLES, "public, max-age=60"));
 
     let config = Config::new(["/api-docs/openapi.json"]);
     let swagger_ui = SwaggerUi::new("/swagger-ui")
@@ -46,7 +181,38 @@ pub fn create_router(pool: PgPool) -> Router {
     app.nest("/projects", projects_router)
         .nest("/jobs", jobs_router)
         .nest("/skills", skills_router)
+        .nest("/export", export_router)
+        .nest("/feeds", feeds_router)
+        .nest("/seo", seo_router)
+        .merge(sitemap_router)
+        .merge(pages_router)
+        .route("/graphql", get(graphiql).post(graphql))
+        .route("/cache/stats", get(get_cache_stats))
+        .route("/metrics", get(get_metrics))
         .merge(swagger_ui)
+        .fallback(route_not_found)
+        .method_not_allowed_fallback(method_not_allowed)
+        .layer(middleware::from_fn_with_state(
+            state.degraded.clone(),
+            serve_stale,
+        ))
+        // Probes report the live state, never the snapshot, and a failing readiness
+        // check does not mark the database unavailable
+        .route("/healthz", get(get_healthz))
+        .route("/readyz", get(get_readyz))
+        .layer(middleware::from_fn(track_requests))
         .layer(cors)
-        .with_state(pool)
+        .layer(middleware::from_fn(request_id))
+        .with_state(state)
+}
+
+async fn route_not_found() -> ApiError {
+    ApiError::NotFound("No route matches the requested path".to_string())
+}
+
+async fn method_not_allowed() -> ApiError {
+    ApiError::InvalidRequest {
+        status: StatusCode::METHOD_NOT_ALLOWED,
+        detail: "Method not allowed for this route".to_string(),
+    }
 }
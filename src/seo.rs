@@ -0,0 +1,290 @@
+//! schema.org JSON-LD and a sitemap, so search engines can index the public site
+//! built on this API.
+
+use crate::export::json_resume::{lines, non_empty};
+use crate::models::feed::ProjectActivity;
+use crate::models::job::Job;
+use crate::models::profile::Profile;
+use crate::models::skill::Skill;
+use crate::models::validation::validate_http_url;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::fmt::Write;
+use utoipa::ToSchema;
+
+const SCHEMA_ORG: &str = "https://schema.org";
+
+/// The public site whose pages the JSON-LD and sitemap point to
+#[derive(Debug, Clone)]
+pub struct SiteConfig {
+    /// Absolute URL without a trailing slash, e.g. `https://example.com`
+    base_url: String,
+}
+
+impl SiteConfig {
+    /// `base_url` must be an absolute http(s) URL
+    pub fn new(base_url: &str) -> Result<Self, String> {
+        validate_http_url("site base URL", base_url)?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Home page, which also identifies the person
+    pub fn home_url(&self) -> String {
+        format!("{}/", self.base_url)
+    }
+
+    pub fn project_url(&self, project_id: i32) -> String {
+        format!("{}/projects/{}", self.base_url, project_id)
+    }
+
+    pub fn job_url(&self, job_id: i32) -> String {
+        format!("{}/jobs/{}", self.base_url, job_id)
+    }
+
+    /// `@id` of the person, so projects can refer to their author
+    fn person_id(&self) -> String {
+        format!("{}/#person", self.base_url)
+    }
+}
+
+/// A schema.org `Person` describing the portfolio's owner
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonLd {
+    #[serde(rename = "@context")]
+    #[schema(example = "https://schema.org")]
+    pub context: &'static str,
+    #[serde(rename = "@type")]
+    #[schema(example = "Person")]
+    pub kind: &'static str,
+    #[serde(rename = "@id")]
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    pub url: String,
+    /// The profile's accounts on other sites
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub same_as: Vec<String>,
+    /// Employers of the current jobs
+    pub works_for: Vec<OrganizationLd>,
+    /// One occupation per job, most recent first
+    pub has_occupation: Vec<OccupationLd>,
+    /// Every skill, by name
+    pub knows_about: Vec<ThingLd>,
+}
+
+/// A schema.org `Organization`
+#[derive(Serialize, Debug, ToSchema)]
+pub struct OrganizationLd {
+    #[serde(rename = "@type")]
+    #[schema(example = "Organization")]
+    pub kind: &'static str,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// A schema.org `Occupation`, built from a job
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OccupationLd {
+    #[serde(rename = "@type")]
+    #[schema(example = "Occupation")]
+    pub kind: &'static str,
+    /// The job's roles, or the company name when it has none
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Responsibilities, one per line of the job's responsibilities
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub responsibilities: Vec<String>,
+    pub hiring_organization: OrganizationLd,
+    /// Page of the job on the public site
+    pub url: String,
+}
+
+/// A schema.org `Thing`, built from a skill
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ThingLd {
+    #[serde(rename = "@type")]
+    #[schema(example = "Thing")]
+    pub kind: &'static str,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// A schema.org `CreativeWork` describing a project
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreativeWorkLd {
+    #[serde(rename = "@context")]
+    #[schema(example = "https://schema.org")]
+    pub context: &'static str,
+    #[serde(rename = "@type")]
+    #[schema(example = "CreativeWork")]
+    pub kind: &'static str,
+    #[serde(rename = "@id")]
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Page of the project on the public site
+    pub url: String,
+    /// The project's GitHub repository
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub same_as: Vec<String>,
+    /// Names of the skills the project uses, comma-separated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    pub author: PersonRefLd,
+    pub date_created: DateTime<Utc>,
+    pub date_modified: DateTime<Utc>,
+}
+
+/// A reference to the `Person` served at `/seo/person.jsonld`
+#[derive(Serialize, Debug, ToSchema)]
+pub struct PersonRefLd {
+    #[serde(rename = "@id")]
+    pub id: String,
+}
+
+impl PersonLd {
+    /// Builds the person from the profile, jobs (most recent first) and skills
+    pub fn new(site: &SiteConfig, profile: &Profile, jobs: &[Job], skills: &[Skill]) -> Self {
+        Self {
+            context: SCHEMA_ORG,
+            kind: "Person",
+            id: site.person_id(),
+            name: non_empty(&profile.name),
+            job_title: profile.label.clone(),
+            description: profile.summary.clone(),
+            email: profile.email.clone(),
+            image: profile.image.clone(),
+            url: profile.url.clone().unwrap_or_else(|| site.home_url()),
+            same_as: profile
+                .profiles
+                .iter()
+                .filter_map(|account| account.url.clone())
+                .collect(),
+            works_for: jobs
+                .iter()
+                .filter(|job| job.is_current_job)
+                .map(organization)
+                .collect(),
+            has_occupation: jobs
+                .iter()
+                .map(|job| OccupationLd {
+                    kind: "Occupation",
+                    name: non_empty(&job.roles).unwrap_or_else(|| job.company_name.clone()),
+                    description: non_empty(&job.description),
+                    responsibilities: lines(&job.responsibilities),
+                    hiring_organization: organization(job),
+                    url: site.job_url(job.id),
+                })
+                .collect(),
+            knows_about: skills
+                .iter()
+                .map(|skill| ThingLd {
+                    kind: "Thing",
+                    name: skill.name.clone(),
+                    description: non_empty(&skill.description),
+                    url: non_empty(&skill.official_site_url),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CreativeWorkLd {
+    pub fn new(site: &SiteConfig, activity: &ProjectActivity) -> Self {
+        let project = &activity.project;
+        let url = site.project_url(project.id);
+        let skills: Vec<&str> = project.skills.iter().map(|s| s.name.as_str()).collect();
+
+        Self {
+            context: SCHEMA_ORG,
+            kind: "CreativeWork",
+            id: url.clone(),
+            name: project.name.clone(),
+            description: non_empty(&project.description),
+            url,
+            same_as: project.github_url.iter().cloned().collect(),
+            keywords: (!skills.is_empty()).then(|| skills.join(", ")),
+            author: PersonRefLd {
+                id: site.person_id(),
+            },
+            date_created: activity.created_at,
+            date_modified: activity.updated_at,
+        }
+    }
+}
+
+fn organization(job: &Job) -> OrganizationLd {
+    OrganizationLd {
+        kind: "Organization",
+        name: job.company_name.clone(),
+        url: non_empty(&job.company_website),
+    }
+}
+
+/// Renders a sitemap of the home page and every project and job page.
+///
+/// Pages are given as (ID, last modified) pairs; the home page is as recent as the
+/// newest of them.
+pub fn sitemap(
+    site: &SiteConfig,
+    projects: &[(i32, DateTime<Utc>)],
+    jobs: &[(i32, DateTime<Utc>)],
+) -> String {
+    let mut urls: Vec<(String, Option<NaiveDate>)> = Vec::new();
+    let newest = projects.iter().chain(jobs).map(|(_, date)| *date).max();
+    urls.push((site.home_url(), newest.map(|date| date.date_naive())));
+    urls.extend(
+        projects
+            .iter()
+            .map(|(id, date)| (site.project_url(*id), Some(date.date_naive()))),
+    );
+    urls.extend(
+        jobs.iter()
+            .map(|(id, date)| (site.job_url(*id), Some(date.date_naive()))),
+    );
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for (loc, lastmod) in urls {
+        xml.push_str("  <url>\n");
+        let _ = writeln!(xml, "    <loc>{}</loc>", escape_xml(&loc));
+        if let Some(lastmod) = lastmod {
+            let _ = writeln!(xml, "    <lastmod>{}</lastmod>", lastmod);
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
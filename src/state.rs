@@ -0,0 +1,58 @@
+use crate::degraded::DegradedMode;
+use crate::graphql::{PortfolioSchema, build_schema};
+use crate::config::{DEFAULT_CACHE_TTL, DEFAULT_READINESS_TIMEOUT};
+use crate::health::Health;
+use crate::models::profile::Profile;
+use crate::render::Themes;
+use crate::response_cache::ResponseCache;
+use crate::seo::SiteConfig;
+use axum::extract::FromRef;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+/// Shared state of the router. Handlers extract only the parts they need, e.g.
+/// `State<PgPool>`.
+#[derive(Clone, FromRef)]
+pub struct AppState {
+    pub pool: PgPool,
+    pub profile: Arc<Profile>,
+    pub themes: Arc<Themes>,
+    pub site: Arc<SiteConfig>,
+    pub graphql: PortfolioSchema,
+    pub cache: ResponseCache,
+    pub degraded: DegradedMode,
+    pub health: Health,
+}
+
+impl AppState {
+    pub fn new(pool: PgPool, profile: Profile, themes: Themes, site: SiteConfig) -> Self {
+        Self {
+            graphql: build_schema(pool.clone()),
+            pool,
+            profile: Arc::new(profile),
+            themes: Arc::new(themes),
+            site: Arc::new(site),
+            cache: ResponseCache::new(DEFAULT_CACHE_TTL),
+            degraded: DegradedMode::new(None),
+            health: Health::new(DEFAULT_READINESS_TIMEOUT),
+        }
+    }
+
+    /// Replaces the response cache, e.g. with one from [`crate::config::load_cache`]
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Replaces degraded mode, e.g. with one from [`crate::config::load_degraded_mode`]
+    pub fn with_degraded_mode(mut self, degraded: DegradedMode) -> Self {
+        self.degraded = degraded;
+        self
+    }
+
+    /// Replaces the health probes, e.g. with ones from [`crate::config::load_health`]
+    pub fn with_health(mut self, health: Health) -> Self {
+        self.health = health;
+        self
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use sqlx::postgres::PgPoolOptions;
+use portfolio_api::degraded::{DegradedMode, Snapshot};
+use portfolio_api::render::Themes;
+use portfolio_api::routes::create_router;
+use portfolio_api::state::AppState;
+use crate::integration::test_utils::{empty_request, get_test_db_pool, setup_test_state, test_profile, test_site};
+
+fn snapshot_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("portfolio-snapshot-{}-{}.json", std::process::id(), name))
+}
+
+/// Takes a snapshot at `path` through a router backed by the test database.
+async fn capture_snapshot(path: &Path) {
+    let degraded = DegradedMode::new(Some(path.to_path_buf()));
+    let state = setup_test_state(Themes::builtin()).await.with_degraded_mode(degraded.clone());
+    let router = create_router(state.clone());
+    let count = degraded.capture(&router, &state).await.expect("Failed to take snapshot");
+    assert!(count > 0);
+}
+
+#[tokio::test]
+async fn test_snapshot_is_served_while_unavailable() {
+    // Arrange: Take a snapshot through a live router
+    let path = snapshot_path("serve");
+    let degraded = DegradedMode::new(Some(path.clone()));
+    let state = setup_test_state(Themes::builtin()).await.with_degraded_mode(degraded.clone());
+    let router = create_router(state.clone());
+    degraded.capture(&router, &state).await.expect("Failed to take snapshot");
+
+    // Assert: The file covers listings, single resources and exports
+    let snapshot = Snapshot::load(&path).expect("Snapshot file is readable");
+    for uri in ["/jobs", "/projects/1", "/skills/1/descendants", "/export/resume.pdf", "/render/classic"] {
+        assert!(snapshot.responses.contains_key(uri), "snapshot has {}", uri);
+    }
+
+    // Act: Fetch while the database is up, then while it is marked down
+    let live = router.clone().oneshot(empty_request("GET", "/jobs")).await.unwrap();
+    degraded.mark_unavailable();
+    let stale = router.clone().oneshot(empty_request("GET", "/jobs")).await.unwrap();
+    let pdf = router.clone().oneshot(empty_request("GET", "/export/resume.pdf")).await.unwrap();
+
+    // Assert: Live responses are not marked; stale ones are, and replay the snapshot
+    assert!(live.headers().get("x-data-stale").is_none());
+    assert_eq!(stale.status(), StatusCode::OK);
+    assert_eq!(stale.headers()["warning"], "110 - \"Response is Stale\"");
+    assert_eq!(
+        stale.headers()["x-data-stale"].to_str().unwrap(),
+        snapshot.taken_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    );
+    assert_eq!(stale.headers()["content-type"], "application/json");
+    let body = axum::body::to_bytes(stale.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body.to_vec(), STANDARD.decode(&snapshot.responses["/jobs"].body).unwrap());
+    assert_eq!(pdf.headers()["content-type"], "application/pdf");
+
+    // Cleanup: Remove the snapshot
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_unreachable_database_serves_snapshot() {
+    // Arrange: Take a snapshot, then load it into a router whose database is unreachable
+    let path = snapshot_path("unreachable");
+    capture_snapshot(&path).await;
+    let degraded = DegradedMode::new(Some(path.clone()));
+    assert!(degraded.load_snapshot().unwrap().is_some());
+    let pool = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(1))
+        .connect_lazy("postgres://postgres@127.0.0.1:1/portfolio")
+        .unwrap();
+    let state = AppState::new(pool, test_profile(), Themes::builtin(), test_site())
+        .with_degraded_mode(degraded.clone());
+    let router = create_router(state);
+
+    // Act: Fetch a skill in the snapshot and one that is not
+    let known = router.clone().oneshot(empty_request("GET", "/skills/1")).await.unwrap();
+    let unknown = router.clone().oneshot(empty_request("GET", "/skills/999999")).await.unwrap();
+
+    // Assert: The failure marks the database down; the snapshot answers what it covers
+    assert!(!degraded.is_available());
+    assert_eq!(known.status(), StatusCode::OK);
+    assert!(known.headers().contains_key("x-data-stale"));
+    assert_eq!(unknown.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    // Cleanup: Remove the snapshot
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_reconnect_marks_database_available() {
+    // Arrange: Mark a reachable database as down
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    let degraded = DegradedMode::new(None);
+    degraded.mark_unavailable();
+
+    // Act: Start the reconnect task
+    degraded.spawn_reconnect(pool);
+
+    // Assert: It finds the database and marks it available
+    tokio::time::timeout(Duration::from_secs(5), degraded.wait_until_available())
+        .await
+        .expect("database marked available");
+    assert!(degraded.is_available());
+}
@@ -1,5 +1,5 @@
 
-use portfolio_api::db::connection::{connect, connect_with_config};
+use portfolio_api::db::connection::{ACQUIRE_TIMEOUT, connect, connect_with_config};
 use dotenv::dotenv;
 
 #[tokio::test]
@@ -9,6 +9,10 @@ async fn test_database_connection() {
 
     let result = connect().await;
     assert!(result.is_ok(), "Database connection failed: {:?}", result.err());
+
+    // Requests give up on a pool connection quickly, so an outage turns into 503s
+    // and the snapshot instead of hanging requests
+    assert_eq!(result.unwrap().options().get_acquire_timeout(), ACQUIRE_TIMEOUT);
 }
 
 #[tokio::test]
@@ -0,0 +1,151 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::Value;
+use crate::integration::test_utils::{empty_request, setup_router_with_test_db};
+
+async fn get_body(router: &axum::Router, uri: &str) -> (axum::http::HeaderMap, Vec<u8>) {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "GET {}", uri);
+    let headers = response.headers().clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (headers, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_json_resume_export() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Export the résumé
+    let (_, body) = get_body(&router, "/export/json-resume").await;
+    let resume: Value = serde_json::from_slice(&body).expect("Failed to parse response body");
+
+    // Assert: basics comes from the configured profile
+    assert!(resume["$schema"].as_str().unwrap().contains("jsonresume"));
+    assert_eq!(resume["basics"]["name"], "Test Person");
+    assert_eq!(resume["basics"]["profiles"][0]["network"], "GitHub");
+
+    // Assert: Jobs map to work entries, and only past jobs have an end date
+    let work = resume["work"].as_array().unwrap();
+    assert!(!work.is_empty());
+    assert!(work.iter().all(|job| job["name"].is_string() && job["startDate"].is_string()));
+    assert!(work.iter().any(|job| job.get("endDate").is_none()));
+    let start_dates: Vec<&str> = work.iter().map(|job| job["startDate"].as_str().unwrap()).collect();
+    assert!(start_dates.windows(2).all(|w| w[0] >= w[1]), "Most recent job first");
+
+    // Assert: Sub-skills become keywords of their top-level skill
+    let skills = resume["skills"].as_array().unwrap();
+    let web = skills.iter().find(|skill| skill["name"] == "Web").expect("Web is top-level");
+    let keywords: Vec<&str> = web["keywords"].as_array().unwrap().iter().map(|k| k.as_str().unwrap()).collect();
+    assert_eq!(keywords, vec!["JavaScript", "React", "TypeScript"]);
+    assert!(skills.iter().all(|skill| skill["name"] != "React"));
+    let levels = ["Beginner", "Intermediate", "Advanced", "Expert"];
+    assert!(skills.iter().all(|skill| levels.contains(&skill["level"].as_str().unwrap())));
+
+    // Assert: Projects carry their skill names as keywords
+    let projects = resume["projects"].as_array().unwrap();
+    assert!(!projects.is_empty());
+    assert!(projects.iter().any(|project| !project["keywords"].as_array().unwrap().is_empty()));
+    assert!(projects.iter().any(|project| project["url"].is_string()));
+}
+
+#[tokio::test]
+async fn test_pdf_resume_export() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Export the résumé in both page sizes
+    let (headers, a4) = get_body(&router, "/export/resume.pdf").await;
+    let (_, letter) = get_body(&router, "/export/resume.pdf?page_size=letter").await;
+
+    // Assert: Both are PDF documents of the requested size
+    assert_eq!(headers["content-type"], "application/pdf");
+    assert!(headers["content-disposition"].to_str().unwrap().contains("resume.pdf"));
+    assert!(a4.starts_with(b"%PDF-"));
+    assert!(letter.starts_with(b"%PDF-"));
+    let a4_text = String::from_utf8_lossy(&a4);
+    let letter_text = String::from_utf8_lossy(&letter);
+    assert!(a4_text.contains("/MediaBox [0 0 595.28 841.89]"));
+    assert!(letter_text.contains("/MediaBox [0 0 612 792]"));
+    assert!(a4_text.contains("/BaseFont /Helvetica"));
+}
+
+#[tokio::test]
+async fn test_pdf_resume_sections_and_projects() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Export only selected sections and projects
+    let (_, all) = get_body(&router, "/export/resume.pdf").await;
+    let (_, selected) = get_body(&router, "/export/resume.pdf?sections=skills,projects&projects=1,2").await;
+
+    // Assert: The smaller résumé renders and is shorter
+    assert!(selected.starts_with(b"%PDF-"));
+    assert!(selected.len() < all.len());
+
+    // Act & Assert: Unknown sections, bad project IDs and page sizes are rejected
+    for uri in [
+        "/export/resume.pdf?sections=work,bogus",
+        "/export/resume.pdf?projects=1,x",
+        "/export/resume.pdf?page_size=a5",
+    ] {
+        let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "GET {}", uri);
+    }
+}
+
+#[tokio::test]
+async fn test_markdown_resume_export() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Export the résumé as Markdown
+    let (headers, body) = get_body(&router, "/export/resume.md").await;
+    let markdown = String::from_utf8(body).unwrap();
+
+    // Assert: Sections appear in the default order with formatted dates
+    assert!(headers["content-type"].to_str().unwrap().starts_with("text/markdown"));
+    assert!(markdown.starts_with("# Test Person\n"));
+
+    // Assert: The contact line lists the profile's links, including social profiles
+    assert!(markdown.contains(
+        "[test@example.com](mailto:test@example.com) · [https://example.com](https://example.com) · [GitHub](https://github.com/test-person)\n"
+    ));
+    let experience = markdown.find("## Experience").expect("Experience section");
+    let skills = markdown.find("## Skills").expect("Skills section");
+    let projects = markdown.find("## Projects").expect("Projects section");
+    assert!(experience < skills && skills < projects);
+    assert!(markdown.contains("– Present*"), "The current job has no end date");
+
+    // Assert: Projects with a GitHub URL link to it
+    assert!(markdown.contains("### [Project 2](https://github.com/"));
+    assert!(markdown.contains("### Project 1\n"));
+    assert!(markdown.contains("- **Web** ("));
+}
+
+#[tokio::test]
+async fn test_plain_text_resume_export() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Export only the projects section, for two projects
+    let (headers, body) = get_body(&router, "/export/resume.txt?sections=projects&projects=2,3").await;
+    let text = String::from_utf8(body).unwrap();
+
+    // Assert: Only the requested section and projects are rendered
+    assert!(headers["content-type"].to_str().unwrap().starts_with("text/plain"));
+    assert!(text.contains("PROJECTS\n========\n"));
+    assert!(text.contains("test@example.com | https://example.com | GitHub: https://github.com/test-person\n"));
+    assert!(!text.contains("EXPERIENCE") && !text.contains("SKILLS"));
+    assert!(text.contains("Project 2\nhttps://github.com/"));
+    assert!(text.contains("Project 3\n"));
+    assert!(!text.contains("Project 1\n"));
+
+    // Act & Assert: Unknown sections are rejected
+    let response = router
+        .clone()
+        .oneshot(empty_request("GET", "/export/resume.txt?sections=education"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
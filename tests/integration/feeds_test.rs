@@ -0,0 +1,184 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::json;
+use chrono::{DateTime, Utc};
+use portfolio_api::models::feed::JobActivity;
+use portfolio_api::models::project::Project;
+use crate::integration::test_utils::{empty_request, get_test_db_pool, json_request, setup_router_with_test_db};
+
+async fn get_feed(router: &axum::Router, uri: &str, content_type: &str) -> Vec<u8> {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "GET {}", uri);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with(content_type));
+    axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()
+}
+
+async fn project_timestamps(pool: &sqlx::PgPool, project_id: i32) -> (DateTime<Utc>, DateTime<Utc>) {
+    sqlx::query_as("SELECT created_at, updated_at FROM projects WHERE id = $1")
+        .bind(project_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_projects_atom_feed() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch the projects feed
+    let body = get_feed(&router, "/feeds/projects.atom", "application/atom+xml").await;
+    let feed = atom_syndication::Feed::read_from(&body[..]).expect("Failed to parse Atom feed");
+
+    // Assert: The feed is titled after the profile and lists projects, most recently changed first
+    assert_eq!(feed.title.as_str(), "Test Person – Projects");
+    assert!(!feed.entries.is_empty());
+    let updated: Vec<_> = feed.entries.iter().map(|entry| entry.updated).collect();
+    assert!(updated.windows(2).all(|w| w[0] >= w[1]));
+    assert_eq!(feed.updated, updated[0]);
+
+    // Assert: Entries link to GitHub and carry skill names as categories
+    let project_2 = feed
+        .entries
+        .iter()
+        .find(|entry| entry.id == "urn:portfolio-api:project:2")
+        .expect("Project 2 is in the feed");
+    assert_eq!(project_2.title.as_str(), "Project 2");
+    assert!(project_2.links[0].href.starts_with("https://github.com/"));
+    assert!(!project_2.categories.is_empty());
+    assert!(project_2.published.is_some());
+
+    // Assert: Every entry has an alternate link, falling back to the site's project page
+    assert!(feed.entries.iter().all(|entry| entry.links.iter().any(|link| link.rel == "alternate")));
+    let project_1 = feed.entries.iter().find(|entry| entry.id == "urn:portfolio-api:project:1").unwrap();
+    assert_eq!(project_1.links[0].href, "https://portfolio.example.com/projects/1");
+}
+
+#[tokio::test]
+async fn test_activity_rss_feed() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch the activity feed
+    let body = get_feed(&router, "/feeds/activity.rss", "application/rss+xml").await;
+    let channel = rss::Channel::read_from(&body[..]).expect("Failed to parse RSS feed");
+
+    // Assert: Job changes and new projects are items, newest first
+    assert_eq!(channel.title, "Test Person – Activity");
+    assert_eq!(channel.link, "https://example.com");
+    assert!(channel.items.len() <= 50);
+    let titles: Vec<&str> = channel.items.iter().filter_map(|item| item.title.as_deref()).collect();
+    assert!(titles.iter().any(|title| title.starts_with("New project: ")));
+    let dates: Vec<DateTime<chrono::FixedOffset>> = channel
+        .items
+        .iter()
+        .map(|item| DateTime::parse_from_rfc2822(item.pub_date.as_deref().unwrap()).unwrap())
+        .collect();
+    assert!(dates.windows(2).all(|w| w[0] >= w[1]));
+    assert!(channel.items.iter().all(|item| item.guid.as_ref().is_some_and(|guid| !guid.permalink)));
+}
+
+#[tokio::test]
+async fn test_activity_feed_includes_job_changes() {
+    // Arrange: Set up the router with test DB; the feed is capped at 50 items, so
+    // check the jobs against the unlimited list of job events
+    let router = setup_router_with_test_db().await;
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    let jobs = portfolio_api::db::jobs_db::fetch_job_activity(&pool).await.unwrap();
+    let items = {
+        let channel = portfolio_api::feeds::activity_rss(
+            &crate::integration::test_utils::test_profile(),
+            &crate::integration::test_utils::test_site(),
+            &[],
+            &jobs,
+            usize::MAX,
+        );
+        rss::Channel::read_from(channel.as_bytes()).unwrap().items
+    };
+
+    // Assert: Every job has a start event and past jobs an end event
+    let current = jobs.iter().find(|activity| activity.job.is_current_job).unwrap();
+    let started = format!("urn:portfolio-api:job:{}:started", current.job.id);
+    let ended = format!("urn:portfolio-api:job:{}:ended", current.job.id);
+    assert!(items.iter().any(|item| item.guid.as_ref().unwrap().value == started));
+    assert!(!items.iter().any(|item| item.guid.as_ref().unwrap().value == ended));
+    let past_jobs = jobs.iter().filter(|activity| !activity.job.is_current_job).count();
+    let edited_jobs = jobs
+        .iter()
+        .filter(|activity| activity.updated_at - activity.created_at > chrono::Duration::minutes(1))
+        .count();
+    assert_eq!(items.len(), jobs.len() + past_jobs + edited_jobs);
+
+    // Act: Render a job edited a day after it was added
+    let edited = JobActivity {
+        job: current.job.clone(),
+        created_at: current.updated_at - chrono::Duration::days(1),
+        updated_at: current.updated_at,
+    };
+    let channel = portfolio_api::feeds::activity_rss(
+        &crate::integration::test_utils::test_profile(),
+        &crate::integration::test_utils::test_site(),
+        &[],
+        std::slice::from_ref(&edited),
+        usize::MAX,
+    );
+
+    // Assert: The edit is an event of its own
+    let updated = format!("urn:portfolio-api:job:{}:updated:{}", edited.job.id, edited.updated_at.timestamp());
+    assert!(channel.contains(&updated), "{}", channel);
+    assert!(channel.contains(&format!("Updated job at {}", edited.job.company_name)));
+
+    // Act & Assert: The router serves the same feed
+    get_feed(&router, "/feeds/activity.rss", "application/rss+xml").await;
+}
+
+#[tokio::test]
+async fn test_project_timestamps_track_changes() {
+    // Arrange: Set up the router with test DB and create a project
+    let router = setup_router_with_test_db().await;
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/projects",
+            json!({ "name": "Feed Test Project", "github_url": "https://github.com/example/feed-test" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let project: Project = serde_json::from_slice(&body).unwrap();
+    let (created_at, updated_at) = project_timestamps(&pool, project.id).await;
+    assert!(updated_at >= created_at);
+
+    // Act: Link a skill to the project
+    let response = router
+        .clone()
+        .oneshot(empty_request("POST", &format!("/projects/{}/skills/4", project.id)))
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    // Assert: Linking the skill bumped updated_at but not created_at
+    let (created_after_link, updated_after_link) = project_timestamps(&pool, project.id).await;
+    assert_eq!(created_after_link, created_at);
+    assert!(updated_after_link > updated_at);
+
+    // Assert: The project is in the Atom feed with its skill
+    let body = get_feed(&router, "/feeds/projects.atom", "application/atom+xml").await;
+    let feed = atom_syndication::Feed::read_from(&body[..]).unwrap();
+    let id = format!("urn:portfolio-api:project:{}", project.id);
+    if let Some(entry) = feed.entries.iter().find(|entry| entry.id == id) {
+        assert_eq!(entry.links[0].href, "https://github.com/example/feed-test");
+        assert_eq!(entry.categories[0].term, "Rust");
+    }
+
+    // Cleanup: Delete the project
+    let response = router
+        .clone()
+        .oneshot(empty_request("DELETE", &format!("/projects/{}", project.id)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
@@ -2,6 +2,7 @@ use hyper::{Request, StatusCode};
 use tower::ServiceExt; // For testing axum routes
 use serde_json;
 use portfolio_api::models::job::Job;
+use portfolio_api::models::page::Page;
 use crate::integration::test_utils::setup_router_with_test_db;
 
 #[tokio::test]
@@ -26,7 +27,8 @@ async fn test_fetch_jobs_integration() {
 
     // Parse and verify the response body
     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let jobs: Vec<Job> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let page: Page<Job> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let jobs = page.items;
 
     assert!(!jobs.is_empty(), "Jobs should not be empty");
     println!("Fetched jobs: {:?}", jobs);
@@ -110,19 +112,13 @@ async fn test_jobs_method_not_allowed() {
     // Arrange: Set up the router with test DB
     let router = setup_router_with_test_db().await;
 
-    // Act: Simulate HTTP POST request to a GET-only endpoint
+    // Act: Simulate HTTP DELETE request to the jobs collection
     let response = router
         .oneshot(
             Request::builder()
-                .method("POST")
+                .method("DELETE")
                 .uri("/jobs")
-                .header("Content-Type", "application/json")
-                .body(axum::body::Body::from(
-                    serde_json::json!({
-                        "title": "Test Job",
-                        "company": "Test Company"
-                    }).to_string()
-                ))
+                .body(axum::body::Body::empty())
                 .unwrap(),
         )
         .await
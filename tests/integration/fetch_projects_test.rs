@@ -1,7 +1,8 @@
 use hyper::Request;
 use tower::ServiceExt; // For testing axum routes
 use serde_json;
-use portfolio_api::models::project::Project;
+use portfolio_api::models::project::{MatchedProject, Project};
+use portfolio_api::models::page::Page;
 use crate::integration::test_utils::setup_router_with_test_db;
 
 #[tokio::test]
@@ -26,7 +27,8 @@ async fn test_fetch_projects_integration() {
 
     // Parse and verify the response body
     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let projects: Vec<Project> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let page: Page<Project> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let projects = page.items;
 
     assert!(!projects.is_empty(), "Projects should not be empty");
     println!("Fetched projects: {:?}", projects);
@@ -85,7 +87,8 @@ async fn test_fetch_projects_by_job_integration() {
 
     // Parse and verify the response body
     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let projects: Vec<Project> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let page: Page<Project> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let projects = page.items;
 
     // Verify that all returned projects have the specified job_id
     for project in &projects {
@@ -120,7 +123,8 @@ async fn test_fetch_projects_by_skill_integration() {
 
     // Parse and verify the response body
     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let projects: Vec<Project> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let page: Page<Project> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let projects = page.items;
 
     // We can't easily assert that all projects have this skill without deeper inspection
     // because the skills are inside each project object
@@ -160,3 +164,58 @@ async fn test_fetch_project_by_id_not_found_integration() {
     assert_eq!(response.status(), 404);
 }
 
+
+async fn fetch_matched_projects(router: &axum::Router, uri: &str) -> Vec<MatchedProject> {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "GET {}", uri);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let page: Page<MatchedProject> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    page.items
+}
+
+#[tokio::test]
+async fn test_fetch_projects_by_skill_with_descendants() {
+    // Arrange: Set up the router with test DB; JavaScript (2) has sub-skills React (3)
+    // and TypeScript (11)
+    let router = setup_router_with_test_db().await;
+    let subtree = [2, 3, 11];
+
+    // Act: List projects for JavaScript alone, for each sub-skill, and for the subtree
+    let exact = fetch_matched_projects(&router, "/projects/skill/2?limit=100").await;
+    let mut expected_ids = Vec::new();
+    for skill_id in subtree {
+        let projects =
+            fetch_matched_projects(&router, &format!("/projects/skill/{}?limit=100", skill_id)).await;
+        expected_ids.extend(projects.iter().map(|p| p.project.id));
+    }
+    expected_ids.sort();
+    expected_ids.dedup();
+    let with_descendants =
+        fetch_matched_projects(&router, "/projects/skill/2?include_descendants=true&limit=100").await;
+
+    // Assert: Exact matches only name JavaScript itself
+    assert!(exact.iter().all(|p| p.matched_skill_ids == vec![2]));
+
+    // Assert: The subtree listing is the union of the per-skill listings, and every
+    // project names the sub-skills it matched through
+    let ids: Vec<i32> = with_descendants.iter().map(|p| p.project.id).collect();
+    assert_eq!(ids, expected_ids);
+    assert!(with_descendants.len() > exact.len());
+    for matched in &with_descendants {
+        assert!(!matched.matched_skill_ids.is_empty());
+        for skill_id in &matched.matched_skill_ids {
+            assert!(subtree.contains(skill_id));
+            assert!(matched.project.skills.iter().any(|s| s.id == *skill_id));
+        }
+    }
+}
@@ -3,6 +3,7 @@ use hyper::Request;
 use tower::ServiceExt; // For testing axum routes
 use serde_json;
 use portfolio_api::models::skill::Skill;
+use portfolio_api::models::page::Page;
 use crate::integration::test_utils::setup_router_with_test_db;
 
 #[tokio::test]
@@ -27,7 +28,8 @@ async fn test_fetch_skills_integration() {
 
     // Parse and verify the response body
     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-    let skills: Vec<Skill> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let page: Page<Skill> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let skills = page.items;
 
     assert!(!skills.is_empty(), "Skills should not be empty");
     println!("Fetched skills: {:?}", skills);
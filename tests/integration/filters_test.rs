@@ -0,0 +1,155 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use chrono::NaiveDate;
+use portfolio_api::models::job::Job;
+use portfolio_api::models::page::Page;
+use portfolio_api::models::project::Project;
+use portfolio_api::models::skill::Skill;
+use serde_json::json;
+use crate::integration::test_utils::{empty_request, json_request, setup_router_with_test_db};
+
+async fn get_items<T: serde::de::DeserializeOwned>(router: &axum::Router, uri: &str) -> Vec<T> {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "GET {}", uri);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let page: Page<T> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    page.items
+}
+
+#[tokio::test]
+async fn test_filter_jobs_by_current_and_from() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+    let from = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+
+    // Act: Fetch current jobs, past jobs, and jobs held since 2020
+    let current: Vec<Job> = get_items(&router, "/jobs?current=true&limit=100").await;
+    let past: Vec<Job> = get_items(&router, "/jobs?current=false&limit=100").await;
+    let since: Vec<Job> = get_items(&router, "/jobs?from=2020-01-01&limit=100").await;
+    let all: Vec<Job> = get_items(&router, "/jobs?limit=100").await;
+
+    // Assert: Each listing only holds matching jobs, and current + past covers everything
+    assert!(current.iter().all(|job| job.is_current_job));
+    assert!(past.iter().all(|job| !job.is_current_job));
+    assert_eq!(current.len() + past.len(), all.len());
+    assert!(since.iter().all(|job| job.end_date.is_none_or(|end| end >= from)));
+    let expected = all.iter().filter(|job| job.end_date.is_none_or(|end| end >= from)).count();
+    assert_eq!(since.len(), expected);
+}
+
+#[tokio::test]
+async fn test_filter_skills_by_proficiency_and_parent() {
+    // Arrange: Set up the router with test DB and pick a skill that has children
+    let router = setup_router_with_test_db().await;
+    let all: Vec<Skill> = get_items(&router, "/skills?limit=100").await;
+    let parent_id = all.iter().find_map(|skill| skill.parent_id).expect("Expected a child skill");
+
+    // Act: Filter by minimum proficiency and by parent
+    let advanced: Vec<Skill> = get_items(&router, "/skills?proficiency_min=Advanced&limit=100").await;
+    let children: Vec<Skill> =
+        get_items(&router, &format!("/skills?parent_id={}&limit=100", parent_id)).await;
+
+    // Assert: Only Advanced/Expert skills and only direct children are returned
+    for skill in &advanced {
+        let level = serde_json::to_value(&skill.proficiency).unwrap();
+        assert!(level == "Advanced" || level == "Expert", "{} is {}", skill.name, level);
+    }
+    assert!(!children.is_empty());
+    assert!(children.iter().all(|skill| skill.parent_id == Some(parent_id)));
+}
+
+#[tokio::test]
+async fn test_filter_projects_by_skills_and_github() {
+    // Arrange: Set up the router with test DB and pick a project using two skills
+    let router = setup_router_with_test_db().await;
+    let all: Vec<Project> = get_items(&router, "/projects?limit=100").await;
+    let target = all
+        .iter()
+        .find(|project| project.skills.len() >= 2)
+        .expect("Expected a project with two skills");
+    let (first, second) = (&target.skills[0].name, &target.skills[1].name);
+    let skills = format!("{},{}", first.to_uppercase(), second.to_lowercase());
+
+    // Act: Filter by both skills (all and any) and by GitHub URL presence
+    let matching_all: Vec<Project> =
+        get_items(&router, &format!("/projects?skills={}&match=all&limit=100", skills)).await;
+    let matching_any: Vec<Project> =
+        get_items(&router, &format!("/projects?skills={}&limit=100", skills)).await;
+    let with_github: Vec<Project> = get_items(&router, "/projects?has_github=true&limit=100").await;
+    let without_github: Vec<Project> =
+        get_items(&router, "/projects?has_github=false&limit=100").await;
+
+    // Assert: Names match case-insensitively and `all` is stricter than `any`
+    let uses = |project: &Project, name: &str| project.skills.iter().any(|s| s.name == name);
+    assert!(matching_all.iter().any(|project| project.id == target.id));
+    assert!(matching_all.iter().all(|project| uses(project, first) && uses(project, second)));
+    assert!(matching_any.iter().all(|project| uses(project, first) || uses(project, second)));
+    assert!(matching_any.len() >= matching_all.len());
+    assert!(with_github.iter().all(|project| project.github_url.is_some()));
+    assert!(without_github.iter().all(|project| project.github_url.is_none()));
+    assert_eq!(with_github.len() + without_github.len(), all.len());
+}
+
+/// Creates a resource, returning its ID.
+async fn create(router: &axum::Router, uri: &str, body: serde_json::Value) -> i64 {
+    let response = router.clone().oneshot(json_request("POST", uri, body)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED, "POST {}", uri);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    created["id"].as_i64().unwrap()
+}
+
+#[tokio::test]
+async fn test_match_all_counts_names_not_skill_rows() {
+    // Arrange: Link a project to two skills whose names differ only in case
+    let router = setup_router_with_test_db().await;
+    let mut skill_ids = Vec::new();
+    for name in ["Duplicate Filter Skill", "duplicate filter skill"] {
+        let skill = json!({ "name": name, "official_site_url": "https://dup.example", "proficiency": "Beginner" });
+        skill_ids.push(create(&router, "/skills", skill).await);
+    }
+    let project_id = create(
+        &router,
+        "/projects",
+        json!({ "name": "Duplicate Skill Names Project", "skill_ids": skill_ids }),
+    )
+    .await;
+
+    // Act: Require the duplicated name plus a skill the project does not use
+    let both: Vec<Project> = get_items(
+        &router,
+        "/projects?skills=duplicate%20filter%20skill,Cobol&match=all&limit=100",
+    )
+    .await;
+    let one: Vec<Project> =
+        get_items(&router, "/projects?skills=duplicate%20filter%20skill&match=all&limit=100").await;
+
+    // Assert: Two rows with the same name count as one requested skill
+    assert!(!both.iter().any(|project| project.id as i64 == project_id));
+    assert!(one.iter().any(|project| project.id as i64 == project_id));
+
+    // Cleanup: Remove the project and skills
+    router.clone().oneshot(empty_request("DELETE", &format!("/projects/{}", project_id))).await.unwrap();
+    for skill_id in skill_ids {
+        router.clone().oneshot(empty_request("DELETE", &format!("/skills/{}", skill_id))).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_filters_are_rejected() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    for uri in [
+        "/skills?proficiency_min=Wizard",
+        "/jobs?from=yesterday",
+        "/jobs?current=maybe",
+        "/projects?match=some",
+    ] {
+        // Act: Request the listing with a malformed filter
+        let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+
+        // Assert: The filter is rejected rather than ignored
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "GET {}", uri);
+    }
+}
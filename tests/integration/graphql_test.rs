@@ -0,0 +1,144 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::{Value, json};
+use portfolio_api::graphql::MAX_BATCH_SIZE;
+use crate::integration::test_utils::{empty_request, json_request, setup_router_with_test_db};
+
+async fn post_graphql(router: &axum::Router, body: Value) -> Value {
+    let response = router.clone().oneshot(json_request("POST", "/graphql", body)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).expect("Failed to parse response body")
+}
+
+#[tokio::test]
+async fn test_graphql_nested_project_fields() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch projects with their job, the job's projects, and skill parents in one query
+    let response = post_graphql(
+        &router,
+        json!({
+            "query": "{ projects { id jobId job { id companyName projects { id jobId } } skills { id parentId parent { id } } } }"
+        }),
+    )
+    .await;
+
+    // Assert: Every nested field resolves to the related records
+    assert!(response.get("errors").is_none(), "{}", response);
+    let projects = response["data"]["projects"].as_array().unwrap();
+    assert!(!projects.is_empty());
+    for project in projects {
+        match project["jobId"].as_i64() {
+            Some(job_id) => {
+                assert_eq!(project["job"]["id"].as_i64(), Some(job_id));
+                let job_projects = project["job"]["projects"].as_array().unwrap();
+                assert!(job_projects.iter().all(|p| p["jobId"].as_i64() == Some(job_id)));
+                assert!(job_projects.iter().any(|p| p["id"] == project["id"]));
+            }
+            None => assert!(project["job"].is_null()),
+        }
+        for skill in project["skills"].as_array().unwrap() {
+            assert_eq!(skill["parent"]["id"].as_i64(), skill["parentId"].as_i64());
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_graphql_skill_hierarchy_and_projects() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Walk down from Web (1) and list the projects using its sub-skills
+    let response = post_graphql(
+        &router,
+        json!({
+            "query": "query Web($id: Int!) { skill(id: $id) { name proficiency children { name parent { name } children { name projects { id skills { id } } } } } }",
+            "variables": { "id": 1 }
+        }),
+    )
+    .await;
+
+    // Assert: Children are ordered by name and projects are linked to the skill
+    assert!(response.get("errors").is_none(), "{}", response);
+    let web = &response["data"]["skill"];
+    assert_eq!(web["name"], "Web");
+    assert!(web["proficiency"].is_string());
+    let javascript = &web["children"][0];
+    assert_eq!(javascript["name"], "JavaScript");
+    assert_eq!(javascript["parent"]["name"], "Web");
+    let grandchildren: Vec<&str> = javascript["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|skill| skill["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(grandchildren, vec!["React", "TypeScript"]);
+    let react_projects = javascript["children"][0]["projects"].as_array().unwrap();
+    assert!(!react_projects.is_empty());
+    assert!(react_projects.iter().all(|project| {
+        project["skills"].as_array().unwrap().iter().any(|skill| skill["id"] == 3)
+    }));
+
+    // Act & Assert: Unknown IDs resolve to null
+    let response = post_graphql(&router, json!({ "query": "{ job(id: 999999) { id } skill(id: 999999) { id } }" })).await;
+    assert!(response["data"]["job"].is_null() && response["data"]["skill"].is_null());
+}
+
+#[tokio::test]
+async fn test_graphql_errors_batches_and_graphiql() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act & Assert: Unknown fields are reported as GraphQL errors
+    let response = post_graphql(&router, json!({ "query": "{ jobs { salary } }" })).await;
+    assert!(response["errors"][0]["message"].as_str().unwrap().contains("salary"));
+
+    // Act & Assert: Queries nested deeper than the limit are rejected
+    let mut query = "id".to_string();
+    for _ in 0..20 {
+        query = format!("id children {{ {} }}", query);
+    }
+    let response = post_graphql(&router, json!({ "query": format!("{{ skills {{ {} }} }}", query) })).await;
+    assert!(response["data"].is_null());
+    assert!(!response["errors"].as_array().unwrap().is_empty());
+
+    // Act & Assert: Lists nested back and forth are rejected for their complexity
+    let mut query = "id".to_string();
+    for _ in 0..3 {
+        query = format!("id skills {{ id projects {{ {} }} }}", query);
+    }
+    let response = post_graphql(&router, json!({ "query": format!("{{ projects {{ {} }} }}", query) })).await;
+    assert!(response["data"].is_null());
+    assert!(response["errors"][0]["message"].as_str().unwrap().contains("complex"), "{}", response);
+
+    // Act & Assert: Aliases count towards the complexity too
+    let aliases: Vec<String> = (0..50).map(|i| format!("a{}: skills {{ id projects {{ id }} }}", i)).collect();
+    let response = post_graphql(&router, json!({ "query": format!("{{ {} }}", aliases.join(" ")) })).await;
+    assert!(response["data"].is_null());
+
+    // Act & Assert: Batches over the limit are rejected outright
+    let batch: Vec<Value> = (0..=MAX_BATCH_SIZE).map(|_| json!({ "query": "{ jobs { id } }" })).collect();
+    let response = router.clone().oneshot(json_request("POST", "/graphql", Value::Array(batch))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Act & Assert: Several requests can be sent as a batch
+    let response = post_graphql(
+        &router,
+        json!([{ "query": "{ jobs { id } }" }, { "query": "{ skills { id } }" }]),
+    )
+    .await;
+    assert!(response[0]["data"]["jobs"].is_array());
+    assert!(response[1]["data"]["skills"].is_array());
+
+    // Act & Assert: Malformed bodies are problem+json errors
+    let response = router.clone().oneshot(json_request("POST", "/graphql", json!({ "query": 5 }))).await.unwrap();
+    assert!(response.status().is_client_error());
+
+    // Act & Assert: GET serves GraphiQL
+    let response = router.clone().oneshot(empty_request("GET", "/graphql")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(String::from_utf8_lossy(&body).contains("graphiql"));
+}
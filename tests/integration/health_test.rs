@@ -0,0 +1,148 @@
+use std::time::{Duration, Instant};
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use sqlx::postgres::PgPoolOptions;
+use portfolio_api::db::connection::expected_migration_version;
+use portfolio_api::health::Health;
+use portfolio_api::render::Themes;
+use portfolio_api::routes::create_router;
+use portfolio_api::state::AppState;
+use crate::integration::test_utils::{empty_request, setup_test_state, test_profile, test_site};
+
+/// Fetches `uri`, returning its status and JSON body.
+async fn probe(router: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    let status = response.status();
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+/// The check named `name` in a health report
+fn check<'a>(report: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    report["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|check| check["name"] == name)
+        .unwrap_or_else(|| panic!("report has a {} check: {}", name, report))
+}
+
+#[tokio::test]
+async fn test_ready_with_migrated_database() {
+    // Arrange: Set up the router over the migrated test database
+    let router = create_router(setup_test_state(Themes::builtin()).await);
+
+    // Act: Probe liveness and readiness
+    let (live_status, live) = probe(&router, "/healthz").await;
+    let (ready_status, ready) = probe(&router, "/readyz").await;
+
+    // Assert: Both pass, and every readiness check reports its status and duration
+    assert_eq!(live_status, StatusCode::OK);
+    assert_eq!(live["status"], "pass");
+    assert_eq!(ready_status, StatusCode::OK, "{}", ready);
+    assert_eq!(ready["status"], "pass");
+    for name in ["shutdown", "database", "migrations"] {
+        assert_eq!(check(&ready, name)["status"], "pass");
+        assert!(check(&ready, name)["duration_ms"].is_number());
+    }
+    let expected = expected_migration_version().unwrap().to_string();
+    assert!(check(&ready, "migrations")["detail"].as_str().unwrap().contains(&expected));
+}
+
+#[tokio::test]
+async fn test_not_ready_during_shutdown() {
+    // Arrange: Set up the router with its own health probes
+    let health = Health::new(Duration::from_secs(2));
+    let state = setup_test_state(Themes::builtin()).await.with_health(health.clone());
+    let degraded = state.degraded.clone();
+    let router = create_router(state);
+
+    // Act: Begin shutting down, then probe
+    health.begin_shutdown();
+    let (live_status, _) = probe(&router, "/healthz").await;
+    let (ready_status, ready) = probe(&router, "/readyz").await;
+
+    // Assert: Only readiness fails, and the database is not mistaken for down
+    assert_eq!(live_status, StatusCode::OK);
+    assert_eq!(ready_status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(ready["status"], "fail");
+    assert_eq!(check(&ready, "shutdown")["status"], "fail");
+    assert_eq!(check(&ready, "database")["status"], "pass");
+    assert!(degraded.is_available());
+}
+
+#[tokio::test]
+async fn test_unreachable_database_fails_within_deadline() {
+    // Arrange: Set up a router whose "database" accepts connections but never answers
+    let silent = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("postgres://postgres@{}/portfolio", silent.local_addr().unwrap());
+    let pool = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(10))
+        .connect_lazy(&url)
+        .unwrap();
+    let state = AppState::new(pool, test_profile(), Themes::builtin(), test_site())
+        .with_health(Health::new(Duration::from_millis(300)));
+    let router = create_router(state);
+
+    // Act: Probe readiness
+    let start = Instant::now();
+    let (status, ready) = probe(&router, "/readyz").await;
+
+    // Assert: It gives up at the deadline instead of waiting for the pool
+    assert!(start.elapsed() < Duration::from_secs(5), "took {:?}", start.elapsed());
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(check(&ready, "database")["status"], "fail");
+    assert_eq!(check(&ready, "database")["detail"], "Timed out after 300 ms");
+    assert_eq!(check(&ready, "migrations")["status"], "fail");
+    assert_eq!(check(&ready, "shutdown")["status"], "pass");
+}
+
+/// Sends `GET uri` over a new connection to `addr`, returning the raw response.
+async fn raw_get(addr: std::net::SocketAddr, uri: &str) -> String {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("server accepts connections");
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", uri);
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn test_readiness_fails_while_draining() {
+    // Arrange: Serve the router with a shutdown triggered by a channel and a drain period
+    let health = Health::new(Duration::from_secs(2));
+    let router = create_router(setup_test_state(Themes::builtin()).await.with_health(health.clone()));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (trigger, signal) = tokio::sync::oneshot::channel::<()>();
+    let shutdown = health.graceful_shutdown(
+        async {
+            signal.await.ok();
+        },
+        Duration::from_millis(500),
+    );
+    let server = tokio::spawn(async move {
+        axum::serve(listener, router).with_graceful_shutdown(shutdown).await.unwrap();
+    });
+    assert!(raw_get(addr, "/readyz").await.starts_with("HTTP/1.1 200"));
+
+    // Act: Trigger shutdown, then probe during the drain
+    trigger.send(()).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let ready = raw_get(addr, "/readyz").await;
+    let live = raw_get(addr, "/healthz").await;
+
+    // Assert: New probes are still accepted, and only readiness fails
+    assert!(ready.starts_with("HTTP/1.1 503"), "{}", ready);
+    assert!(ready.contains("Server is shutting down"));
+    assert!(live.starts_with("HTTP/1.1 200"), "{}", live);
+
+    // Assert: The server stops once the drain is over
+    tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .expect("server shut down after the drain")
+        .unwrap();
+}
@@ -0,0 +1,192 @@
+use axum::body::Body;
+use axum::http::{HeaderMap, Response};
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::json;
+use crate::integration::test_utils::{empty_request, json_request, setup_router_with_test_db};
+
+/// Sends a GET with one extra header.
+async fn conditional_get(router: &axum::Router, uri: &str, name: &str, value: &str) -> Response<Body> {
+    let mut request = empty_request("GET", uri);
+    request.headers_mut().insert(
+        axum::http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+        value.parse().unwrap(),
+    );
+    router.clone().oneshot(request).await.unwrap()
+}
+
+/// Fetches `uri`, then revalidates it with a precondition built from the response's
+/// headers. Retries when a concurrent test changed the data in between.
+async fn revalidate(
+    router: &axum::Router,
+    uri: &str,
+    name: &str,
+    precondition: impl Fn(&HeaderMap) -> String,
+) -> (HeaderMap, Response<Body>) {
+    let mut last = None;
+    for _ in 0..5 {
+        let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "GET {}", uri);
+        let headers = response.headers().clone();
+
+        let revalidated = conditional_get(router, uri, name, &precondition(&headers)).await;
+        if revalidated.status() == StatusCode::NOT_MODIFIED {
+            return (headers, revalidated);
+        }
+        last = Some((headers, revalidated));
+    }
+    last.unwrap()
+}
+
+#[tokio::test]
+async fn test_get_sets_validators_and_cache_control() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch a resource and a feed
+    let jobs = router.clone().oneshot(empty_request("GET", "/jobs")).await.unwrap();
+    let feed = router.clone().oneshot(empty_request("GET", "/feeds/activity.rss")).await.unwrap();
+
+    // Assert: Both carry a strong ETag, a Last-Modified date and their group's Cache-Control
+    assert_eq!(jobs.status(), StatusCode::OK);
+    let etag = jobs.headers()["etag"].to_str().unwrap();
+    assert!(etag.starts_with('"') && etag.ends_with('"'), "strong ETag: {}", etag);
+    let last_modified = jobs.headers()["last-modified"].to_str().unwrap();
+    assert!(chrono::DateTime::parse_from_rfc2822(last_modified).is_ok(), "{}", last_modified);
+    assert_eq!(jobs.headers()["cache-control"], "public, max-age=60");
+    assert_eq!(feed.headers()["cache-control"], "public, max-age=900");
+
+    // Assert: Different URIs get different ETags
+    assert_ne!(jobs.headers()["etag"], feed.headers()["etag"]);
+}
+
+#[tokio::test]
+async fn test_if_none_match_returns_not_modified() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Revalidate with the ETag just received
+    let (headers, response) = revalidate(&router, "/skills/1", "if-none-match", |headers| {
+        headers["etag"].to_str().unwrap().to_string()
+    })
+    .await;
+
+    // Assert: 304 with an empty body, repeating the validators
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()["etag"], headers["etag"]);
+    assert_eq!(response.headers()["cache-control"], "public, max-age=60");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(body.is_empty());
+
+    // Act: Revalidate with a weak form of the ETag among others
+    let (_, response) = revalidate(&router, "/skills/1", "if-none-match", |headers| {
+        format!("\"other\", W/{}", headers["etag"].to_str().unwrap())
+    })
+    .await;
+
+    // Assert: Weak comparison matches too
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    // Act: Send an ETag that never matches, and `*`
+    let stale = conditional_get(&router, "/skills/1", "if-none-match", "\"stale\"").await;
+    let any = conditional_get(&router, "/skills/1", "if-none-match", "*").await;
+
+    // Assert: A mismatch gets the full response; `*` matches the existing skill
+    assert_eq!(stale.status(), StatusCode::OK);
+    assert_eq!(any.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn test_if_modified_since() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Revalidate with the Last-Modified date just received
+    let (_, response) = revalidate(&router, "/export/json-resume", "if-modified-since", |headers| {
+        headers["last-modified"].to_str().unwrap().to_string()
+    })
+    .await;
+
+    // Assert: Unchanged since then
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()["cache-control"], "public, max-age=300");
+
+    // Act: Ask with a date before any data was written
+    let response = conditional_get(
+        &router,
+        "/export/json-resume",
+        "if-modified-since",
+        "Thu, 01 Jan 1970 00:00:00 GMT",
+    )
+    .await;
+
+    // Assert: Full response
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Act: Send a mismatching If-None-Match with a future If-Modified-Since
+    let mut request = empty_request("GET", "/export/json-resume");
+    request.headers_mut().insert("if-none-match", "\"stale\"".parse().unwrap());
+    request
+        .headers_mut()
+        .insert("if-modified-since", "Fri, 01 Jan 2100 00:00:00 GMT".parse().unwrap());
+    let response = router.clone().oneshot(request).await.unwrap();
+
+    // Assert: If-None-Match takes precedence
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_write_changes_etag() {
+    // Arrange: Set up the router with test DB and note the ETag of the job list
+    let router = setup_router_with_test_db().await;
+    let before = router.clone().oneshot(empty_request("GET", "/jobs")).await.unwrap();
+    let etag = before.headers()["etag"].to_str().unwrap().to_string();
+
+    // Act: Add a job, then revalidate the list
+    let created = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/jobs",
+            json!({
+                "start_date": "2001-01-01",
+                "end_date": "2001-06-30",
+                "company_name": "Cache Test Co"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(created.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(created.into_body(), usize::MAX).await.unwrap();
+    let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let after = conditional_get(&router, "/jobs", "if-none-match", &etag).await;
+
+    // Assert: The old ETag no longer matches
+    assert_eq!(after.status(), StatusCode::OK);
+    assert_ne!(after.headers()["etag"].to_str().unwrap(), etag);
+
+    // Cleanup: Remove the job
+    let uri = format!("/jobs/{}", job["id"]);
+    router.clone().oneshot(empty_request("DELETE", &uri)).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_errors_and_writes_carry_no_validators() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch a missing project and send a write
+    let missing = router.clone().oneshot(empty_request("GET", "/projects/999999")).await.unwrap();
+    let write = router
+        .clone()
+        .oneshot(json_request("PATCH", "/jobs/999999", json!({"company_name": "X"})))
+        .await
+        .unwrap();
+
+    // Assert: Neither is cacheable
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    assert!(missing.headers().get("etag").is_none());
+    assert!(missing.headers().get("cache-control").is_none());
+    assert!(write.headers().get("etag").is_none());
+}
@@ -0,0 +1,72 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use portfolio_api::metrics::{Untracked, track_requests};
+use crate::integration::test_utils::{empty_request, setup_router_with_test_db};
+
+/// Fetches `/metrics`, returning its body.
+async fn scrape(router: &axum::Router) -> String {
+    let response = router.clone().oneshot(empty_request("GET", "/metrics")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_requests_are_labelled_by_route_template() {
+    // Arrange: Set up the router
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch a project, a missing project and an unknown path, then scrape
+    let found = router.clone().oneshot(empty_request("GET", "/projects/1")).await.unwrap();
+    let missing = router.clone().oneshot(empty_request("GET", "/projects/999999")).await.unwrap();
+    router.clone().oneshot(empty_request("GET", "/no-such-page/42")).await.unwrap();
+    let metrics = scrape(&router).await;
+
+    // Assert: Requests are counted per template and status class, never per raw path
+    assert_eq!(found.status(), StatusCode::OK);
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    assert!(metrics.contains(r#"http_requests_total{method="GET",route="/projects/{project_id}",status="2xx"}"#));
+    assert!(metrics.contains(r#"http_requests_total{method="GET",route="/projects/{project_id}",status="4xx"}"#));
+    assert!(metrics.contains(r#"http_requests_total{method="GET",route="unmatched",status="4xx"}"#));
+    assert!(metrics.contains(r#"http_request_duration_seconds_bucket{method="GET",route="/projects/{project_id}""#));
+    assert!(!metrics.contains("/projects/1\""));
+    assert!(!metrics.contains("/no-such-page"));
+}
+
+#[tokio::test]
+async fn test_database_metrics_are_exposed() {
+    // Arrange: Set up the router
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch a project, then scrape
+    router.clone().oneshot(empty_request("GET", "/projects/1")).await.unwrap();
+    let metrics = scrape(&router).await;
+
+    // Assert: The query was timed and the pool statistics are present
+    assert!(metrics.contains(r#"db_query_duration_seconds_count{query="projects_db::fetch_project_by_id"}"#));
+    for gauge in ["db_pool_connections ", "db_pool_idle_connections ", "db_pool_max_connections "] {
+        assert!(metrics.contains(gauge), "metrics have {}", gauge);
+    }
+    assert!(metrics.contains("db_pool_acquire_timeouts_total "));
+}
+
+#[tokio::test]
+async fn test_untracked_requests_are_not_counted() {
+    // Arrange: A router with its own routes behind the tracking middleware
+    let router = axum::Router::new()
+        .route("/metrics-test/untracked", axum::routing::get(|| async { "untracked" }))
+        .route("/metrics-test/tracked", axum::routing::get(|| async { "tracked" }))
+        .layer(axum::middleware::from_fn(track_requests));
+    let mut untracked = empty_request("GET", "/metrics-test/untracked");
+    untracked.extensions_mut().insert(Untracked);
+
+    // Act: Send one request marked untracked, as snapshot captures are, and one not
+    router.clone().oneshot(untracked).await.unwrap();
+    router.clone().oneshot(empty_request("GET", "/metrics-test/tracked")).await.unwrap();
+    let metrics = scrape(&setup_router_with_test_db().await).await;
+
+    // Assert: Only the unmarked request is counted
+    assert!(metrics.contains(r#"route="/metrics-test/tracked""#));
+    assert!(!metrics.contains("/metrics-test/untracked"));
+}
@@ -0,0 +1,19 @@
+use portfolio_api::db::connection::{MIGRATOR, run_migrations};
+use crate::integration::test_utils::get_test_db_pool;
+
+#[tokio::test]
+async fn test_migrations_are_applied_and_idempotent() {
+    // Arrange: Connect to the test DB
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+
+    // Act: Apply the embedded migrations twice
+    run_migrations(&pool).await.expect("First migration run failed");
+    run_migrations(&pool).await.expect("Second migration run failed");
+
+    // Assert: Every embedded migration is recorded as successfully applied
+    let applied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations WHERE success")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(applied, MIGRATOR.iter().count() as i64);
+}
@@ -2,4 +2,26 @@ mod env_connection_test;
 mod fetch_skills_test;
 mod test_utils;
 mod fetch_projects_test;
-mod fetch_jobs_tests;
\ No newline at end of file
+mod fetch_jobs_tests;
+mod write_jobs_test;
+mod write_projects_test;
+mod write_skills_test;
+mod auth_test;
+mod migrations_test;
+mod problem_details_test;
+mod row_decoding_test;
+mod pagination_test;
+mod filters_test;
+mod search_test;
+mod skill_hierarchy_test;
+mod export_test;
+mod render_test;
+mod feeds_test;
+mod seo_test;
+mod graphql_test;
+mod http_cache_test;
+mod response_cache_test;
+mod degraded_test;
+mod project_query_benchmark_test;
+mod metrics_test;
+mod health_test;
@@ -0,0 +1,111 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::Value;
+use portfolio_api::models::job::Job;
+use portfolio_api::models::page::Page;
+use portfolio_api::models::skill::Skill;
+use crate::integration::test_utils::{empty_request, setup_router_with_test_db};
+
+async fn get_page<T: serde::de::DeserializeOwned>(
+    router: &axum::Router,
+    uri: &str,
+) -> (Option<String>, Page<T>) {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "GET {}", uri);
+    let link = response
+        .headers()
+        .get("link")
+        .map(|link| link.to_str().unwrap().to_string());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (link, serde_json::from_slice(&body).expect("Failed to parse response body"))
+}
+
+#[tokio::test]
+async fn test_following_next_links_visits_every_job_once() {
+    // Arrange: Set up the router with test DB and fetch the full listing
+    let router = setup_router_with_test_db().await;
+    let (_, all) = get_page::<Job>(&router, "/jobs?limit=100").await;
+    assert!(all.next_cursor.is_none());
+
+    // Act: Walk the listing three jobs at a time through the Link headers
+    let mut ids = Vec::new();
+    let mut uri = Some("/jobs?limit=3".to_string());
+    while let Some(current) = uri.take() {
+        let (link, page) = get_page::<Job>(&router, &current).await;
+        assert!(page.items.len() <= 3);
+        ids.extend(page.items.iter().map(|job| job.id));
+        assert_eq!(link.is_some(), page.next_cursor.is_some());
+        uri = link.map(|link| {
+            let target = link.trim_start_matches('<').split('>').next().unwrap();
+            assert!(link.ends_with("rel=\"next\""));
+            assert!(target.contains("limit=3"), "Link should keep other parameters");
+            target.to_string()
+        });
+    }
+
+    // Assert: The pages concatenate to the full listing, most recent first
+    let all_ids: Vec<i32> = all.items.iter().map(|job| job.id).collect();
+    assert_eq!(ids, all_ids);
+    assert!(all.items.windows(2).all(|w| w[0].start_date >= w[1].start_date));
+}
+
+#[tokio::test]
+async fn test_sort_by_multiple_fields() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Sort skills by proficiency descending, then name
+    let (_, page) = get_page::<Skill>(&router, "/skills?sort=-proficiency,name&limit=100").await;
+
+    // Assert: Names are ascending within each proficiency level
+    let levels: Vec<String> = page
+        .items
+        .iter()
+        .map(|skill| serde_json::to_value(&skill.proficiency).unwrap().as_str().unwrap().to_string())
+        .collect();
+    for (i, pair) in page.items.windows(2).enumerate() {
+        if levels[i] == levels[i + 1] {
+            assert!(pair[0].name <= pair[1].name, "{} before {}", pair[0].name, pair[1].name);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_sorted_pages_follow_cursor() {
+    // Arrange: Set up the router with test DB and sort projects by name
+    let router = setup_router_with_test_db().await;
+    let (_, all) = get_page::<Value>(&router, "/projects?sort=-name&limit=100").await;
+
+    // Act: Fetch the first two projects, then the page after them
+    let (_, first) = get_page::<Value>(&router, "/projects?sort=-name&limit=2").await;
+    let cursor = first.next_cursor.expect("Expected another page");
+    let (_, second) =
+        get_page::<Value>(&router, &format!("/projects?sort=-name&limit=2&cursor={}", cursor)).await;
+
+    // Assert: The second page continues where the first stopped
+    assert_eq!(first.items, all.items[..2]);
+    assert_eq!(second.items, all.items[2..4]);
+}
+
+#[tokio::test]
+async fn test_invalid_pagination_parameters_are_rejected() {
+    // Arrange: Set up the router with test DB and get a cursor for the default sort
+    let router = setup_router_with_test_db().await;
+    let (_, page) = get_page::<Job>(&router, "/jobs?limit=1").await;
+    let cursor = page.next_cursor.expect("Expected another page");
+
+    for uri in [
+        "/jobs?sort=roles".to_string(),
+        "/jobs?sort=id,-id".to_string(),
+        "/jobs?limit=0".to_string(),
+        "/jobs?limit=101".to_string(),
+        "/jobs?cursor=not-a-cursor".to_string(),
+        format!("/jobs?sort=company_name&cursor={}", cursor),
+    ] {
+        // Act: Request the listing with a bad parameter
+        let response = router.clone().oneshot(empty_request("GET", &uri)).await.unwrap();
+
+        // Assert: The request is rejected rather than silently ignored
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "GET {}", uri);
+    }
+}
@@ -0,0 +1,110 @@
+use hyper::{Request, StatusCode};
+use tower::ServiceExt; // For testing axum routes
+use portfolio_api::error::ProblemDetails;
+use crate::integration::test_utils::{json_request, setup_router_with_test_db};
+
+async fn parse_problem(response: axum::response::Response) -> ProblemDetails {
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).expect("Failed to parse problem details")
+}
+
+#[tokio::test]
+async fn test_not_found_is_problem_json_with_request_id() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Request a missing job with a caller-supplied request id
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/jobs/9999")
+                .header("X-Request-Id", "test-request-42")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Assert: The problem document carries the stable type and the echoed request id
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers().get("x-request-id").unwrap(), "test-request-42");
+    let problem = parse_problem(response).await;
+    assert_eq!(problem.problem_type, "/problems/not-found");
+    assert_eq!(problem.status, 404);
+    assert_eq!(problem.detail, "Job not found");
+    assert_eq!(problem.request_id.as_deref(), Some("test-request-42"));
+}
+
+#[tokio::test]
+async fn test_invalid_path_parameter_is_problem_json() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Use a non-numeric skill id
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/skills/abc")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Assert: Expect a 400 invalid-request problem with a generated request id
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let problem = parse_problem(response).await;
+    assert_eq!(problem.problem_type, "/problems/invalid-request");
+    assert!(problem.request_id.is_some());
+}
+
+#[tokio::test]
+async fn test_validation_failure_is_problem_json() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Create a job missing its end date
+    let response = router
+        .oneshot(json_request(
+            "POST",
+            "/jobs",
+            serde_json::json!({ "start_date": "2020-01-01", "company_name": "No End" }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Expect a 422 validation problem explaining the rule
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let problem = parse_problem(response).await;
+    assert_eq!(problem.problem_type, "/problems/validation");
+    assert!(problem.detail.contains("end_date"));
+}
+
+#[tokio::test]
+async fn test_unauthorized_is_problem_json() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Delete without a token
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri("/skills/1")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Assert: Expect a 401 unauthorized problem
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let problem = parse_problem(response).await;
+    assert_eq!(problem.problem_type, "/problems/unauthorized");
+}
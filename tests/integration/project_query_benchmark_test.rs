@@ -0,0 +1,191 @@
+//! Opt-in benchmark of the project lookups, run with
+//! `cargo test project_query_benchmark -- --ignored --nocapture`.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Executor, PgPool};
+use portfolio_api::db::projects_db;
+use portfolio_api::models::page::PageParams;
+use portfolio_api::models::project::{MatchedProject, Project};
+
+/// Projects seeded for the benchmark
+const PROJECTS: i32 = 5000;
+/// Runs of each lookup per measurement
+const RUNS: u32 = 20;
+
+/// The project query before skills were aggregated per project: the CTE groups the
+/// skills of every project before any filter applies. Kept as the baseline.
+const AGGREGATE_ALL_QUERY: &str = r#"
+    WITH project_skills AS (
+        SELECT
+            p.id as project_id,
+            COALESCE(
+                jsonb_agg(
+                    jsonb_build_object(
+                        'id', s.id,
+                        'name', s.name,
+                        'description', s.description,
+                        'official_site_url', s.official_site_url,
+                        'proficiency', s.proficiency
+                    ) ORDER BY s.name ASC
+                ) FILTER (WHERE s.id IS NOT NULL),
+                '[]'::jsonb
+            ) as skills
+        FROM projects p
+        LEFT JOIN projects_skills ps ON p.id = ps.project_id
+        LEFT JOIN skills s ON ps.skill_id = s.id
+        GROUP BY p.id
+    )
+    SELECT
+        p.id,
+        p.name,
+        p.description,
+        p.github_url,
+        p.job_id,
+        COALESCE(ps.skills, '[]'::jsonb) as skills
+    FROM projects p
+    LEFT JOIN project_skills ps ON p.id = ps.project_id
+"#;
+
+/// Temporary `skills`, `projects` and `projects_skills` tables holding thousands of
+/// projects. They shadow the real tables for the connection only.
+const SEED: &str = r#"
+    CREATE TEMP TABLE skills (LIKE public.skills INCLUDING DEFAULTS);
+    INSERT INTO skills (id, name, description, official_site_url, proficiency)
+    SELECT id, 'Skill ' || id, 'Description ' || id, 'https://skill.example', 'Intermediate'
+    FROM generate_series(1, 50) AS id;
+    ALTER TABLE skills ADD PRIMARY KEY (id);
+    CREATE INDEX ON skills (parent_id);
+
+    CREATE TEMP TABLE projects (LIKE public.projects INCLUDING DEFAULTS);
+    INSERT INTO projects (id, name, description, job_id)
+    SELECT id, 'Project ' || id, 'Description ' || id, id % 100 + 1
+    FROM generate_series(1, 5000) AS id;
+    ALTER TABLE projects ADD PRIMARY KEY (id);
+    CREATE INDEX ON projects (job_id);
+
+    CREATE TEMP TABLE projects_skills (LIKE public.projects_skills INCLUDING DEFAULTS);
+    INSERT INTO projects_skills (project_id, skill_id)
+    SELECT p.id, (p.id + offset_by * 17) % 50 + 1
+    FROM generate_series(1, 5000) AS p(id), generate_series(0, 2) AS offset_by;
+    ALTER TABLE projects_skills ADD PRIMARY KEY (project_id, skill_id);
+    CREATE INDEX ON projects_skills (skill_id);
+
+    ANALYZE skills;
+    ANALYZE projects;
+    ANALYZE projects_skills;
+"#;
+
+/// A single-connection pool whose connection sees the seeded temporary tables, so the
+/// real `projects_db` functions run against them and concurrent tests are unaffected.
+async fn seeded_pool() -> PgPool {
+    dotenv::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    PgPoolOptions::new()
+        .max_connections(1)
+        .after_connect(|conn, _| {
+            Box::pin(async move {
+                conn.execute(SEED).await?;
+                Ok(())
+            })
+        })
+        .connect(&database_url)
+        .await
+        .expect("Failed to seed benchmark tables")
+}
+
+/// Total time of `RUNS` calls of `lookup`, after one warm-up call.
+async fn time<F, Fut>(lookup: F) -> Duration
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = usize>,
+{
+    assert!(lookup().await > 0, "lookup returns rows");
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        lookup().await;
+    }
+    start.elapsed()
+}
+
+/// Times `query` on the baseline, bound to `param`.
+async fn time_baseline(pool: &PgPool, query: String, param: i32) -> Duration {
+    time(|| async {
+        sqlx::query(&query).bind(param).fetch_all(pool).await.unwrap().len()
+    })
+    .await
+}
+
+fn report(name: &str, before: Duration, after: Duration) {
+    println!(
+        "{}: aggregate-all {:?}, per-project {:?} ({} runs over {} projects)",
+        name, before, after, RUNS, PROJECTS
+    );
+}
+
+#[tokio::test]
+#[ignore = "benchmark; run with --ignored"]
+async fn test_project_lookups_scale_with_result_set() {
+    // Arrange: Seed thousands of projects, each with three skills
+    let pool = seeded_pool().await;
+    let page = PageParams::default()
+        .resolve(projects_db::PROJECT_SORT_KEYS, projects_db::PROJECT_DEFAULT_SORT)
+        .unwrap();
+
+    // Act: Time the real lookups against the baseline filtered the same way
+    let by_id_before = time_baseline(
+        &pool,
+        format!("SELECT * FROM ({}) p WHERE p.id = $1", AGGREGATE_ALL_QUERY),
+        PROJECTS / 2,
+    )
+    .await;
+    let by_id_after = time(|| async {
+        let project: Option<Project> =
+            projects_db::fetch_project_by_id(&pool, PROJECTS / 2).await.unwrap();
+        project.into_iter().count()
+    })
+    .await;
+    report("project by id", by_id_before, by_id_after);
+
+    let by_job_before = time_baseline(
+        &pool,
+        format!(
+            "SELECT * FROM ({}) p WHERE p.job_id = $1 ORDER BY p.id LIMIT 51",
+            AGGREGATE_ALL_QUERY
+        ),
+        7,
+    )
+    .await;
+    let by_job_after = time(|| async {
+        let page = projects_db::fetch_projects_by_job::<Project>(&pool, 7, &page).await.unwrap();
+        page.items.len()
+    })
+    .await;
+    report("projects by job", by_job_before, by_job_after);
+
+    let by_skill_before = time_baseline(
+        &pool,
+        format!(
+            "SELECT p.* FROM projects_skills links JOIN ({}) p ON p.id = links.project_id WHERE links.skill_id = $1 ORDER BY p.id LIMIT 51",
+            AGGREGATE_ALL_QUERY
+        ),
+        3,
+    )
+    .await;
+    let by_skill_after = time(|| async {
+        let page = projects_db::fetch_projects_by_skill::<MatchedProject>(&pool, 3, false, &page)
+            .await
+            .unwrap();
+        page.items.len()
+    })
+    .await;
+    report("projects by skill", by_skill_before, by_skill_after);
+
+    // Assert: Filtered listings no longer aggregate every project, so they are far
+    // faster. Postgres already pushed an equality on `id` through the old CTE's
+    // `GROUP BY p.id`, so single-project lookups were cheap and must merely stay so.
+    assert!(by_id_after <= by_id_before * 2, "by id: {:?} vs {:?}", by_id_after, by_id_before);
+    assert!(by_job_after * 3 < by_job_before, "by job: {:?} vs {:?}", by_job_after, by_job_before);
+    assert!(by_skill_after * 3 < by_skill_before, "by skill: {:?} vs {:?}", by_skill_after, by_skill_before);
+}
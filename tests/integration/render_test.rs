@@ -0,0 +1,115 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use portfolio_api::render::Themes;
+use crate::integration::test_utils::{empty_request, setup_router_with_test_db, setup_router_with_themes};
+
+async fn get_page(router: &axum::Router, uri: &str) -> (StatusCode, String) {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_render_builtin_themes() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    for theme in ["classic", "minimal"] {
+        // Act: Render the portfolio page with the theme
+        let response = router
+            .clone()
+            .oneshot(empty_request("GET", &format!("/render/{}", theme)))
+            .await
+            .unwrap();
+
+        // Assert: A complete HTML page with the profile, jobs, skills and projects
+        assert_eq!(response.status(), StatusCode::OK, "theme {}", theme);
+        assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/html"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let page = String::from_utf8(body.to_vec()).unwrap();
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains(&format!("portfolio-api ({} theme)", theme)));
+        assert!(page.contains("<h1>Test Person</h1>"));
+        assert!(page.contains("Company 12"));
+        assert!(page.contains("TypeScript"));
+        assert!(page.contains("github.com"), "Projects link to GitHub");
+        assert!(page.trim_end().ends_with("</html>"));
+    }
+}
+
+#[tokio::test]
+async fn test_render_sections_and_unknown_theme() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Render only the projects section
+    let (status, page) = get_page(&router, "/render/classic?sections=projects&projects=2").await;
+
+    // Assert: Other sections and projects are left out
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains("id=\"projects\""));
+    assert!(!page.contains("id=\"experience\"") && !page.contains("id=\"skills\""));
+    assert!(page.contains("Project 2") && !page.contains("Project 3"));
+
+    // Act & Assert: Unknown themes are 404 and list the available ones
+    let (status, body) = get_page(&router, "/render/fancy").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body.contains("classic, minimal"));
+}
+
+#[tokio::test]
+async fn test_render_theme_loaded_from_disk() {
+    // Arrange: A themes directory with a new theme split across templates, an
+    // override of a built-in theme, and a directory that is not a theme
+    let dir = std::env::temp_dir().join(format!("portfolio-themes-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    for theme in ["plain", "minimal", "notes"] {
+        std::fs::create_dir_all(dir.join(theme)).unwrap();
+    }
+    std::fs::write(
+        dir.join("plain/base.html"),
+        "<html><body>{% block body %}{% endblock %}</body></html>",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("plain/index.html"),
+        r#"{% extends "./base.html" %}{% block body %}<p>{{ profile.name }} ({{ theme }}): {{ projects | length }} projects, {{ "<b>" }}</p>{% endblock %}"#,
+    )
+    .unwrap();
+    std::fs::write(dir.join("minimal/index.html"), "overridden").unwrap();
+    std::fs::write(dir.join("notes/readme.txt"), "not a theme").unwrap();
+
+    let mut themes = Themes::builtin();
+    themes.load_dir(&dir).expect("Failed to load themes");
+    std::fs::remove_dir_all(&dir).unwrap();
+    let router = setup_router_with_themes(themes).await;
+
+    // Act: Render the disk theme and the overridden built-in one
+    let (status, page) = get_page(&router, "/render/plain?projects=1,2").await;
+    let (_, minimal) = get_page(&router, "/render/minimal").await;
+    let (notes_status, _) = get_page(&router, "/render/notes").await;
+
+    // Assert: Templates extend their siblings and values are HTML-escaped
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page, "<html><body><p>Test Person (plain): 2 projects, &lt;b&gt;</p></body></html>");
+    assert_eq!(minimal, "overridden");
+    assert_eq!(notes_status, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn test_load_invalid_theme_fails() {
+    // Arrange: A theme whose template does not parse
+    let dir = std::env::temp_dir().join(format!("portfolio-bad-themes-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("broken")).unwrap();
+    std::fs::write(dir.join("broken/index.html"), "{% if %}").unwrap();
+
+    // Act: Load the directory
+    let result = Themes::builtin().load_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // Assert: Startup fails with the offending file named
+    let error = result.expect_err("Invalid templates are rejected");
+    assert!(error.contains("broken/index.html"), "{}", error);
+}
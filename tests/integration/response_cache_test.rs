@@ -0,0 +1,127 @@
+use std::time::Duration;
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::json;
+use portfolio_api::config::DEFAULT_CACHE_TTL;
+use portfolio_api::response_cache::ResponseCache;
+use crate::integration::test_utils::{empty_request, get_test_db_pool, json_request, setup_router_with_cache};
+
+/// Fetches `uri`, returning its `X-Cache` header and body.
+async fn get_cached(router: &axum::Router, uri: &str) -> (String, Vec<u8>) {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "GET {}", uri);
+    let cache_status = response.headers()["x-cache"].to_str().unwrap().to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (cache_status, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_repeated_get_is_served_from_cache() {
+    // Arrange: Set up the router with its own cache
+    let cache = ResponseCache::new(DEFAULT_CACHE_TTL);
+    let router = setup_router_with_cache(cache.clone()).await;
+
+    // Act: Fetch the same skill twice
+    let (first_status, first_body) = get_cached(&router, "/skills/2").await;
+    let (second_status, second_body) = get_cached(&router, "/skills/2").await;
+
+    // Assert: The second response comes from memory, byte for byte
+    assert_eq!(first_status, "MISS");
+    assert_eq!(second_status, "HIT");
+    assert_eq!(first_body, second_body);
+
+    // Assert: The counters reflect it
+    let response = router.clone().oneshot(empty_request("GET", "/cache/stats")).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(stats["hits"], 1);
+    assert_eq!(stats["misses"], 1);
+    assert_eq!(stats["entries"], 1);
+    assert_eq!(stats["listening"], false);
+    assert_eq!(stats["ttl_seconds"], DEFAULT_CACHE_TTL.as_secs());
+}
+
+#[tokio::test]
+async fn test_write_through_router_invalidates() {
+    // Arrange: Set up the router and cache the job list
+    let router = setup_router_with_cache(ResponseCache::new(DEFAULT_CACHE_TTL)).await;
+    get_cached(&router, "/jobs?limit=100").await;
+    let (status, _) = get_cached(&router, "/jobs?limit=100").await;
+    assert_eq!(status, "HIT");
+
+    // Act: Add a job, then fetch the list again
+    let created = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/jobs",
+            json!({
+                "start_date": "1999-01-01",
+                "end_date": "1999-06-30",
+                "company_name": "Response Cache Co"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(created.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(created.into_body(), usize::MAX).await.unwrap();
+    let job: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let (status, body) = get_cached(&router, "/jobs?limit=100").await;
+
+    // Assert: The stale list was dropped and the new one has the job
+    assert_eq!(status, "MISS");
+    assert!(String::from_utf8(body).unwrap().contains("Response Cache Co"));
+
+    // Cleanup: Remove the job
+    let uri = format!("/jobs/{}", job["id"]);
+    router.clone().oneshot(empty_request("DELETE", &uri)).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_notification_invalidates() {
+    // Arrange: Set up the router with a listening cache and cache a project
+    let cache = ResponseCache::new(DEFAULT_CACHE_TTL);
+    let router = setup_router_with_cache(cache.clone()).await;
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    cache.spawn_listener(pool.clone());
+    for _ in 0..50 {
+        if cache.stats().listening {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(cache.stats().listening, "listener subscribed");
+    get_cached(&router, "/projects/1").await;
+    let (status, _) = get_cached(&router, "/projects/1").await;
+    assert_eq!(status, "HIT");
+
+    // Act: Change the project behind the API's back
+    sqlx::query("UPDATE projects SET name = name WHERE id = 1")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Assert: The notification drops the cached response
+    let mut status = String::new();
+    for _ in 0..50 {
+        (status, _) = get_cached(&router, "/projects/1").await;
+        if status == "MISS" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(status, "MISS");
+}
+
+#[tokio::test]
+async fn test_entries_expire_without_notifications() {
+    // Arrange: Set up the router with a cache that is not listening and has no TTL
+    let router = setup_router_with_cache(ResponseCache::new(Duration::ZERO)).await;
+
+    // Act: Fetch the same job twice
+    get_cached(&router, "/jobs/1").await;
+    let (status, _) = get_cached(&router, "/jobs/1").await;
+
+    // Assert: The entry expired right away
+    assert_eq!(status, "MISS");
+}
@@ -0,0 +1,78 @@
+use hyper::{Request, StatusCode};
+use tower::ServiceExt; // For testing axum routes
+use serde_json::Value;
+use portfolio_api::db::lenient::Lenient;
+use portfolio_api::models::job::Job;
+use portfolio_api::models::page::Page;
+use crate::integration::test_utils::{get_test_db_pool, setup_router_with_test_db};
+
+// A job row whose start_date column has drifted from DATE to TEXT
+const DRIFTED_JOB_QUERY: &str = r#"
+    SELECT 1 AS id, 'not a date'::text AS start_date, NULL::date AS end_date,
+           true AS is_current_job, 'Acme' AS company_name, '' AS company_website,
+           '' AS description, '' AS roles, '' AS responsibilities
+"#;
+
+#[tokio::test]
+async fn test_strict_decoding_fails_on_drifted_column() {
+    // Arrange: Connect to the test DB
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+
+    // Act: Decode the drifted row as a Job
+    let result = sqlx::query_as::<_, Job>(DRIFTED_JOB_QUERY)
+        .fetch_one(&pool)
+        .await;
+
+    // Assert: The decode error names the column instead of fabricating a date
+    match result {
+        Err(sqlx::Error::ColumnDecode { index, .. }) => assert!(index.contains("start_date")),
+        other => panic!("Expected a column decode error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_lenient_decoding_nulls_column_and_warns() {
+    // Arrange: Connect to the test DB
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+
+    // Act: Decode the drifted row leniently
+    let job = sqlx::query_as::<_, Lenient<Job>>(DRIFTED_JOB_QUERY)
+        .fetch_one(&pool)
+        .await
+        .expect("Lenient decoding should not fail");
+
+    // Assert: The bad column is null with a warning, the rest are intact
+    assert_eq!(job.fields["start_date"], Value::Null);
+    assert_eq!(job.fields["company_name"], "Acme");
+    assert_eq!(job.warnings.len(), 1);
+    assert!(job.warnings[0].starts_with("start_date:"));
+}
+
+#[tokio::test]
+async fn test_lenient_query_param_adds_warnings() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch jobs in lenient mode
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/jobs?lenient=true")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Assert: Each job carries an empty warnings list alongside its fields
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let page: Page<Value> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let jobs = page.items;
+    assert!(!jobs.is_empty(), "Expected at least one job");
+    for job in jobs {
+        assert_eq!(job["warnings"], Value::Array(vec![]));
+        assert!(job["id"].as_i64().unwrap() > 0);
+    }
+}
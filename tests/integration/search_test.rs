@@ -0,0 +1,87 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::json;
+use portfolio_api::models::project::Project;
+use portfolio_api::models::search::{SearchHit, SearchKind};
+use crate::integration::test_utils::{empty_request, json_request, setup_router_with_test_db};
+
+async fn search(router: &axum::Router, uri: &str) -> Vec<SearchHit> {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "GET {}", uri);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).expect("Failed to parse response body")
+}
+
+#[tokio::test]
+async fn test_search_finds_new_project_with_highlight() {
+    // Arrange: Create a project with a distinctive word in its description
+    let router = setup_router_with_test_db().await;
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/projects",
+            json!({
+                "name": "Search Test Project",
+                "description": "A <script>alert('x')</script> telescope scheduler for quasarwatch observatories & more",
+                "skill_ids": []
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let project: Project = serde_json::from_slice(&body).unwrap();
+
+    // Act: Search for the word
+    let hits = search(&router, "/search?q=quasarwatch").await;
+
+    // Assert: The project is the only hit and the match is highlighted
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].kind, SearchKind::Project);
+    assert_eq!(hits[0].id, project.id);
+    assert!(hits[0].score > 0.0);
+    assert!(hits[0].highlight.contains("<mark>quasarwatch</mark>"), "{}", hits[0].highlight);
+
+    // Assert: The stored text is escaped, so the highlight is the only markup
+    assert!(hits[0].highlight.contains("alert(&#x27;x&#x27;)"), "{}", hits[0].highlight);
+    assert!(hits[0].highlight.contains("&amp; more"));
+    assert!(!hits[0].highlight.contains("<script>"));
+
+    // Cleanup: Remove the project again
+    let response = router
+        .clone()
+        .oneshot(empty_request("DELETE", &format!("/projects/{}", project.id)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_search_ranks_hits_across_kinds() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Search for a phrase found in skill descriptions
+    let hits = search(&router, "/search?q=web%20framework&limit=5").await;
+
+    // Assert: Skill hits are returned, best score first, within the limit
+    assert!(!hits.is_empty());
+    assert!(hits.len() <= 5);
+    assert!(hits.iter().any(|hit| hit.kind == SearchKind::Skill));
+    assert!(hits.windows(2).all(|w| w[0].score >= w[1].score));
+}
+
+#[tokio::test]
+async fn test_search_requires_query() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    for uri in ["/search", "/search?q=%20%20", "/search?q=rust&limit=0"] {
+        // Act: Search without usable terms
+        let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+
+        // Assert: The request is rejected
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "GET {}", uri);
+    }
+}
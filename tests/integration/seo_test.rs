@@ -0,0 +1,98 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::Value;
+use crate::integration::test_utils::{empty_request, setup_router_with_test_db};
+
+async fn get_body(router: &axum::Router, uri: &str) -> (StatusCode, String, String) {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    let status = response.status();
+    let content_type = response.headers()["content-type"].to_str().unwrap().to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_person_jsonld() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch the person
+    let (status, content_type, body) = get_body(&router, "/seo/person.jsonld").await;
+    let person: Value = serde_json::from_str(&body).expect("Failed to parse JSON-LD");
+
+    // Assert: A schema.org Person built from the profile
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/ld+json");
+    assert_eq!(person["@context"], "https://schema.org");
+    assert_eq!(person["@type"], "Person");
+    assert_eq!(person["@id"], "https://portfolio.example.com/#person");
+    assert_eq!(person["name"], "Test Person");
+    assert_eq!(person["jobTitle"], "Software Engineer");
+    assert_eq!(person["sameAs"][0], "https://github.com/test-person");
+
+    // Assert: Current employers, occupations from jobs and skills as knowsAbout
+    let works_for = person["worksFor"].as_array().unwrap();
+    assert!(!works_for.is_empty());
+    assert!(works_for.iter().all(|org| org["@type"] == "Organization"));
+    let occupations = person["hasOccupation"].as_array().unwrap();
+    assert!(occupations.len() >= works_for.len());
+    assert_eq!(occupations[0]["@type"], "Occupation");
+    assert_eq!(occupations[0]["hiringOrganization"]["name"], works_for[0]["name"]);
+    assert!(occupations[0]["url"].as_str().unwrap().starts_with("https://portfolio.example.com/jobs/"));
+    let knows_about: Vec<&str> = person["knowsAbout"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|thing| thing["name"].as_str().unwrap())
+        .collect();
+    assert!(knows_about.contains(&"Rust") && knows_about.contains(&"React"));
+}
+
+#[tokio::test]
+async fn test_project_jsonld() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch project 2, which has a GitHub URL
+    let (status, content_type, body) = get_body(&router, "/seo/projects/2.jsonld").await;
+    let work: Value = serde_json::from_str(&body).expect("Failed to parse JSON-LD");
+
+    // Assert: A CreativeWork pointing at the site page, its repository and its author
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/ld+json");
+    assert_eq!(work["@type"], "CreativeWork");
+    assert_eq!(work["name"], "Project 2");
+    assert_eq!(work["url"], "https://portfolio.example.com/projects/2");
+    assert!(work["sameAs"][0].as_str().unwrap().starts_with("https://github.com/"));
+    assert_eq!(work["author"]["@id"], "https://portfolio.example.com/#person");
+    assert!(!work["keywords"].as_str().unwrap().is_empty());
+    assert!(work["dateModified"].is_string());
+
+    // Act & Assert: Unknown projects, invalid IDs and other extensions are rejected
+    let (status, _, _) = get_body(&router, "/seo/projects/999999.jsonld").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _, _) = get_body(&router, "/seo/projects/abc.jsonld").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, _) = get_body(&router, "/seo/projects/2.json").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_sitemap() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch the sitemap
+    let (status, content_type, xml) = get_body(&router, "/sitemap.xml").await;
+
+    // Assert: The home page and every project and job page, under the site base URL
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("application/xml"));
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(xml.contains("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">"));
+    assert!(xml.contains("<loc>https://portfolio.example.com/</loc>"));
+    assert!(xml.contains("<loc>https://portfolio.example.com/projects/1</loc>"));
+    assert!(xml.contains("<loc>https://portfolio.example.com/jobs/12</loc>"));
+    assert_eq!(xml.matches("<url>").count(), xml.matches("<lastmod>").count());
+    assert!(xml.trim_end().ends_with("</urlset>"));
+}
@@ -0,0 +1,131 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use portfolio_api::models::skill::{RelatedSkill, SkillNode};
+use crate::integration::test_utils::{empty_request, get_test_db_pool, setup_router_with_test_db};
+
+async fn get_json<T: serde::de::DeserializeOwned>(router: &axum::Router, uri: &str) -> T {
+    let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "GET {}", uri);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).expect("Failed to parse response body")
+}
+
+fn names(skills: &[RelatedSkill]) -> Vec<(&str, i32)> {
+    skills.iter().map(|s| (s.skill.name.as_str(), s.depth)).collect()
+}
+
+#[tokio::test]
+async fn test_skill_tree_nests_children() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Fetch the whole tree
+    let tree: Vec<SkillNode> = get_json(&router, "/skills/tree").await;
+
+    // Assert: Web -> JavaScript -> [React, TypeScript]
+    let web = tree.iter().find(|node| node.skill.name == "Web").expect("Web is top-level");
+    let javascript = web
+        .children
+        .iter()
+        .find(|node| node.skill.name == "JavaScript")
+        .expect("JavaScript is under Web");
+    let grandchildren: Vec<&str> =
+        javascript.children.iter().map(|node| node.skill.name.as_str()).collect();
+    assert_eq!(grandchildren, vec!["React", "TypeScript"]);
+    assert!(tree.iter().all(|node| node.skill.parent_id.is_none()));
+
+    // Act: Limit the tree to top-level skills and their children
+    let shallow: Vec<SkillNode> = get_json(&router, "/skills/tree?max_depth=1").await;
+
+    // Assert: Grandchildren are cut off
+    let web = shallow.iter().find(|node| node.skill.name == "Web").unwrap();
+    assert!(web.children.iter().all(|node| node.children.is_empty()));
+}
+
+#[tokio::test]
+async fn test_skill_ancestors_and_descendants() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Walk up from React and down from Web
+    let ancestors: Vec<RelatedSkill> = get_json(&router, "/skills/3/ancestors").await;
+    let nearest: Vec<RelatedSkill> = get_json(&router, "/skills/3/ancestors?max_depth=1").await;
+    let descendants: Vec<RelatedSkill> = get_json(&router, "/skills/1/descendants").await;
+
+    // Assert: Walks are ordered by depth and respect max_depth
+    assert_eq!(names(&ancestors), vec![("JavaScript", 1), ("Web", 2)]);
+    assert_eq!(names(&nearest), vec![("JavaScript", 1)]);
+    assert_eq!(
+        names(&descendants),
+        vec![("JavaScript", 1), ("React", 2), ("TypeScript", 2)]
+    );
+}
+
+#[tokio::test]
+async fn test_hierarchy_walks_stop_at_cycles() {
+    // Arrange: Create two skills that are each other's parent, bypassing the API checks
+    let router = setup_router_with_test_db().await;
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    let first: i32 = sqlx::query_scalar(
+        "INSERT INTO skills (name, official_site_url) VALUES ('Cycle A', 'https://a.example') RETURNING id",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let second: i32 = sqlx::query_scalar(
+        "INSERT INTO skills (name, official_site_url, parent_id) VALUES ('Cycle B', 'https://b.example', $1) RETURNING id",
+    )
+    .bind(first)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE skills SET parent_id = $1 WHERE id = $2")
+        .bind(second)
+        .bind(first)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Act: Walk the cycle in both directions
+    let ancestors: Vec<RelatedSkill> =
+        get_json(&router, &format!("/skills/{}/ancestors", first)).await;
+    let descendants: Vec<RelatedSkill> =
+        get_json(&router, &format!("/skills/{}/descendants", first)).await;
+
+    // Assert: Each walk visits the other skill once and stops
+    assert_eq!(names(&ancestors), vec![("Cycle B", 1)]);
+    assert_eq!(names(&descendants), vec![("Cycle B", 1)]);
+
+    // Cleanup: Break the cycle and remove both skills
+    sqlx::query("UPDATE skills SET parent_id = NULL WHERE id IN ($1, $2)")
+        .bind(first)
+        .bind(second)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM skills WHERE id IN ($1, $2)")
+        .bind(first)
+        .bind(second)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_hierarchy_rejects_unknown_skill_and_bad_depth() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    for (uri, status) in [
+        ("/skills/9999/ancestors", StatusCode::NOT_FOUND),
+        ("/skills/9999/descendants", StatusCode::NOT_FOUND),
+        ("/skills/1/descendants?max_depth=0", StatusCode::BAD_REQUEST),
+        ("/skills/tree?max_depth=65", StatusCode::BAD_REQUEST),
+    ] {
+        // Act: Request the walk
+        let response = router.clone().oneshot(empty_request("GET", uri)).await.unwrap();
+
+        // Assert: The expected error is returned
+        assert_eq!(response.status(), status, "GET {}", uri);
+    }
+}
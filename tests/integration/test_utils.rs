@@ -1,6 +1,14 @@
 use axum::Router;
+use hyper::Request;
 use sqlx::{PgPool, Error};
 use dotenv::dotenv;
+use portfolio_api::auth::{JOBS_WRITE, PROJECTS_WRITE, SKILLS_WRITE};
+use portfolio_api::db::api_tokens_db::hash_token;
+use portfolio_api::models::profile::{Profile, SocialProfile};
+use portfolio_api::render::Themes;
+use portfolio_api::response_cache::ResponseCache;
+use portfolio_api::seo::SiteConfig;
+use portfolio_api::state::AppState;
 
 /// Helper function to establish a database connection for integration tests.
 ///
@@ -18,12 +26,100 @@ pub async fn get_test_db_pool() -> Result<PgPool, Error> {
     PgPool::connect(&database_url).await
 }
 
+/// Plaintext API token with every write scope, seeded by `setup_router_with_test_db`
+/// and sent by `json_request` / `empty_request`.
+pub const TEST_API_TOKEN: &str = "pat_integration_tests_all_scopes";
+
+/// Stores `TEST_API_TOKEN` unless an earlier test already did.
+async fn seed_test_api_token(pool: &PgPool) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO api_tokens (name, token_hash, scopes)
+        VALUES ('integration tests', $1, $2)
+        ON CONFLICT (token_hash) DO UPDATE SET scopes = EXCLUDED.scopes, revoked_at = NULL
+        "#,
+    )
+    .bind(hash_token(TEST_API_TOKEN))
+    .bind(vec![JOBS_WRITE, PROJECTS_WRITE, SKILLS_WRITE])
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Profile the router is built with, standing in for the `PROFILE_PATH` file.
+pub fn test_profile() -> Profile {
+    Profile {
+        name: "Test Person".to_string(),
+        label: Some("Software Engineer".to_string()),
+        email: Some("test@example.com".to_string()),
+        url: Some("https://example.com".to_string()),
+        summary: Some("Builds things for the integration tests.".to_string()),
+        profiles: vec![SocialProfile {
+            network: "GitHub".to_string(),
+            username: Some("test-person".to_string()),
+            url: Some("https://github.com/test-person".to_string()),
+        }],
+        ..Profile::default()
+    }
+}
+
+/// Public site the router is built with, standing in for `SITE_BASE_URL`.
+pub fn test_site() -> SiteConfig {
+    SiteConfig::new("https://portfolio.example.com/").expect("test site URL is valid")
+}
+
 pub async fn setup_router_with_test_db() -> Router {
+    setup_router_with_themes(Themes::builtin()).await
+}
+
+/// Like `setup_router_with_test_db`, but rendering pages with the given themes.
+pub async fn setup_router_with_themes(themes: Themes) -> Router {
+    portfolio_api::routes::create_router(setup_test_state(themes).await)
+}
+
+/// Like `setup_router_with_test_db`, but caching responses in the given cache.
+pub async fn setup_router_with_cache(cache: ResponseCache) -> Router {
+    let state = setup_test_state(Themes::builtin()).await.with_cache(cache);
+    portfolio_api::routes::create_router(state)
+}
+
+/// Migrated, seeded state with the test profile and site, for tests that build the router themselves.
+pub async fn setup_test_state(themes: Themes) -> AppState {
     // Use your test DB pool setup
     let pool = get_test_db_pool()
         .await
         .expect("Failed to get test DB pool");
 
-    // Pass the test pool to the router
-    portfolio_api::routes::create_router(pool)
-}
\ No newline at end of file
+    portfolio_api::db::connection::run_migrations(&pool)
+        .await
+        .expect("Failed to apply migrations to test DB");
+
+    seed_test_api_token(&pool)
+        .await
+        .expect("Failed to seed test API token");
+
+    // Pass the test pool and profile to the router
+    AppState::new(pool, test_profile(), themes, test_site())
+}
+
+/// Builds an authorized request carrying a JSON body.
+pub fn json_request(method: &str, uri: &str, body: serde_json::Value) -> Request<axum::body::Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", TEST_API_TOKEN))
+        .body(axum::body::Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// Builds an authorized request without a body.
+pub fn empty_request(method: &str, uri: &str) -> Request<axum::body::Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Authorization", format!("Bearer {}", TEST_API_TOKEN))
+        .body(axum::body::Body::empty())
+        .unwrap()
+}
@@ -0,0 +1,222 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::json;
+use portfolio_api::models::job::Job;
+use crate::integration::test_utils::{empty_request, get_test_db_pool, json_request, setup_router_with_test_db};
+
+#[tokio::test]
+async fn test_create_update_delete_job_integration() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Create a past job
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/jobs",
+            json!({
+                "start_date": "2019-02-01",
+                "end_date": "2020-03-01",
+                "company_name": "Write Test Company",
+                "roles": "Developer"
+            }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Expect 201 with a Location header pointing at the new job
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers().get("location").unwrap().to_str().unwrap().to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: Job = serde_json::from_slice(&body).expect("Failed to parse response body");
+    assert_eq!(location, format!("/jobs/{}", created.id));
+    assert_eq!(created.company_name, "Write Test Company");
+
+    // Act: Turn it into the current job by clearing the end date
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "PATCH",
+            &location,
+            json!({ "end_date": null, "is_current_job": true }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: The patch is applied and untouched fields are kept
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let patched: Job = serde_json::from_slice(&body).expect("Failed to parse response body");
+    assert_eq!(patched.end_date, None);
+    assert!(patched.is_current_job);
+    assert_eq!(patched.roles, "Developer");
+
+    // Act: Replace the job entirely
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "PUT",
+            &location,
+            json!({
+                "start_date": "2019-02-01",
+                "end_date": "2021-01-01",
+                "company_name": "Renamed Company"
+            }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Omitted text fields are reset by a full replacement
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let replaced: Job = serde_json::from_slice(&body).expect("Failed to parse response body");
+    assert_eq!(replaced.company_name, "Renamed Company");
+    assert_eq!(replaced.roles, "");
+
+    // Act: Delete the job, then try to fetch it
+    let response = router.clone().oneshot(empty_request("DELETE", &location)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = router.oneshot(empty_request("GET", &location)).await.unwrap();
+
+    // Assert: The job is gone
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_job_inconsistent_dates() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: A current job must not carry an end date
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/jobs",
+            json!({
+                "start_date": "2022-01-01",
+                "end_date": "2023-01-01",
+                "is_current_job": true,
+                "company_name": "Invalid Company"
+            }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Expect 422 Unprocessable Entity
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Act: An end date before the start date
+    let response = router
+        .oneshot(json_request(
+            "POST",
+            "/jobs",
+            json!({
+                "start_date": "2022-01-01",
+                "end_date": "2021-01-01",
+                "company_name": "Invalid Company"
+            }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Expect 422 Unprocessable Entity
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_update_job_not_found() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Replace a non-existent job
+    let response = router
+        .oneshot(json_request(
+            "PUT",
+            "/jobs/9999",
+            json!({
+                "start_date": "2019-02-01",
+                "end_date": "2020-03-01",
+                "company_name": "Nobody"
+            }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Expect a 404 Not Found response
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_job_referenced_by_projects() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Job 1 has projects attached to it
+    let response = router.oneshot(empty_request("DELETE", "/jobs/1")).await.unwrap();
+
+    // Assert: Expect a 409 Conflict response
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_patch_keeps_concurrent_job_update() {
+    // Arrange: Create a past job
+    let router = setup_router_with_test_db().await;
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/jobs",
+            json!({
+                "start_date": "2018-01-01",
+                "end_date": "2019-01-01",
+                "company_name": "Concurrent Patch Company",
+                "roles": "Developer"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let job_id: i32 = location.rsplit('/').next().unwrap().parse().unwrap();
+
+    // Arrange: Change the roles in a transaction that is not yet committed
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("UPDATE jobs SET roles = 'Lead Developer' WHERE id = $1")
+        .bind(job_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    // Act: Patch the company name while the roles change is uncommitted, then commit it
+    let patch = tokio::spawn(
+        router
+            .clone()
+            .oneshot(json_request("PATCH", &location, json!({ "company_name": "Renamed Concurrent Company" }))),
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    tx.commit().await.unwrap();
+    let response = patch.await.unwrap().unwrap();
+
+    // Assert: The patch waited for the roles change instead of overwriting it with a stale read
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let patched: Job = serde_json::from_slice(&body).expect("Failed to parse response body");
+    assert_eq!(patched.company_name, "Renamed Concurrent Company");
+    assert_eq!(patched.roles, "Lead Developer");
+
+    // Act & Assert: The merged job is still validated
+    let response = router
+        .clone()
+        .oneshot(json_request("PATCH", &location, json!({ "end_date": "2017-01-01" })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Cleanup: Remove the job
+    router.oneshot(empty_request("DELETE", &location)).await.unwrap();
+}
@@ -0,0 +1,215 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::json;
+use portfolio_api::models::project::Project;
+use portfolio_api::models::page::Page;
+use crate::integration::test_utils::{empty_request, get_test_db_pool, json_request, setup_router_with_test_db};
+
+async fn parse_project(response: axum::response::Response) -> Project {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).expect("Failed to parse response body")
+}
+
+fn skill_ids(project: &Project) -> Vec<i32> {
+    let mut ids: Vec<i32> = project.skills.iter().map(|s| s.id).collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn test_project_lifecycle_with_skill_links() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Create a project linked to two skills
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/projects",
+            json!({
+                "name": "Write Test Project",
+                "description": "Created by the integration tests",
+                "github_url": "https://github.com/example/write-test",
+                "job_id": 2,
+                "skill_ids": [4, 5]
+            }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: The hydrated project is returned with its skills
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let project = parse_project(response).await;
+    assert_eq!(skill_ids(&project), vec![4, 5]);
+    let uri = format!("/projects/{}", project.id);
+
+    // Act: Link and unlink single skills
+    let response = router
+        .clone()
+        .oneshot(empty_request("POST", &format!("{}/skills/9", uri)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(skill_ids(&parse_project(response).await), vec![4, 5, 9]);
+
+    let response = router
+        .clone()
+        .oneshot(empty_request("DELETE", &format!("{}/skills/4", uri)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(skill_ids(&parse_project(response).await), vec![5, 9]);
+
+    // Act: Replace the whole skill set
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "PUT",
+            &format!("{}/skills", uri),
+            json!({ "skill_ids": [1, 2, 3] }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(skill_ids(&parse_project(response).await), vec![1, 2, 3]);
+
+    // Act: Patch the project without touching its skills
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "PATCH",
+            &uri,
+            json!({ "github_url": null, "job_id": null }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Cleared fields are null and skills are preserved
+    assert_eq!(response.status(), StatusCode::OK);
+    let patched = parse_project(response).await;
+    assert_eq!(patched.github_url, None);
+    assert_eq!(patched.job_id, None);
+    assert_eq!(skill_ids(&patched), vec![1, 2, 3]);
+
+    // Act: Delete the project
+    let response = router.clone().oneshot(empty_request("DELETE", &uri)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = router.oneshot(empty_request("GET", &uri)).await.unwrap();
+
+    // Assert: The project is gone
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_project_with_unknown_skill_is_rolled_back() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Create a project referencing a skill that does not exist
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/projects",
+            json!({ "name": "Rolled Back Project", "skill_ids": [1, 9999] }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Expect 422 and no project left behind
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = router.oneshot(empty_request("GET", "/projects")).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let page: Page<Project> = serde_json::from_slice(&body).expect("Failed to parse response body");
+    let projects = page.items;
+    assert!(!projects.iter().any(|p| p.name == "Rolled Back Project"));
+}
+
+#[tokio::test]
+async fn test_create_project_invalid_github_url() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Create a project with a malformed URL
+    let response = router
+        .oneshot(json_request(
+            "POST",
+            "/projects",
+            json!({ "name": "Bad URL Project", "github_url": "not a url" }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Expect 422 Unprocessable Entity
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_link_skill_to_missing_project() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Link a skill to a non-existent project
+    let response = router
+        .oneshot(empty_request("POST", "/projects/9999/skills/1"))
+        .await
+        .unwrap();
+
+    // Assert: Expect a 404 Not Found response
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_patch_keeps_concurrent_skill_link() {
+    // Arrange: Create a project linked to one skill
+    let router = setup_router_with_test_db().await;
+    let response = router
+        .clone()
+        .oneshot(json_request("POST", "/projects", json!({ "name": "Concurrent Patch Project", "skill_ids": [4] })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let project_id = parse_project(response).await.id;
+    let uri = format!("/projects/{}", project_id);
+
+    // Arrange: Link a second skill in a transaction that holds the project lock, as
+    // `POST /projects/{id}/skills/{skill_id}` does
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("SELECT id FROM projects WHERE id = $1 FOR UPDATE")
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO projects_skills (project_id, skill_id) VALUES ($1, 5)")
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    // Act: Patch the name while the link is uncommitted, then commit the link
+    let patch = tokio::spawn(
+        router
+            .clone()
+            .oneshot(json_request("PATCH", &uri, json!({ "name": "Renamed Concurrent Project" }))),
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    tx.commit().await.unwrap();
+    let response = patch.await.unwrap().unwrap();
+
+    // Assert: The patch waited for the link instead of overwriting it with a stale read
+    assert_eq!(response.status(), StatusCode::OK);
+    let patched = parse_project(response).await;
+    assert_eq!(patched.name, "Renamed Concurrent Project");
+    assert_eq!(skill_ids(&patched), vec![4, 5]);
+
+    // Act & Assert: Supplied fields are still validated
+    let response = router.clone().oneshot(json_request("PATCH", &uri, json!({ "name": " " }))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Cleanup: Remove the project
+    router.oneshot(empty_request("DELETE", &uri)).await.unwrap();
+}
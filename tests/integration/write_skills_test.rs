@@ -0,0 +1,315 @@
+use hyper::StatusCode;
+use tower::ServiceExt; // For testing axum routes
+use serde_json::json;
+use portfolio_api::models::skill::Skill;
+use crate::integration::test_utils::{empty_request, get_test_db_pool, json_request, setup_router_with_test_db};
+
+async fn parse_skill(response: axum::response::Response) -> Skill {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).expect("Failed to parse response body")
+}
+
+#[tokio::test]
+async fn test_skill_hierarchy_writes() {
+    // Arrange: Set up the router with test DB and create a parent with a child
+    let router = setup_router_with_test_db().await;
+
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/skills",
+            json!({
+                "name": "Write Test Parent",
+                "official_site_url": "https://parent.example",
+                "proficiency": "Advanced"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let parent = parse_skill(response).await;
+
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/skills",
+            json!({
+                "name": "Write Test Child",
+                "official_site_url": "https://child.example",
+                "proficiency": "Beginner",
+                "parent_id": parent.id
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let child = parse_skill(response).await;
+    assert_eq!(child.parent_id, Some(parent.id));
+
+    // Act: Make the parent a child of its own child
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "PATCH",
+            &format!("/skills/{}", parent.id),
+            json!({ "parent_id": child.id }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: The cycle is rejected
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Act: Make the parent its own parent
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "PATCH",
+            &format!("/skills/{}", parent.id),
+            json!({ "parent_id": parent.id }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Act: Detach the child, then attach it again
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "PATCH",
+            &format!("/skills/{}", child.id),
+            json!({ "parent_id": null, "proficiency": "Intermediate" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let detached = parse_skill(response).await;
+    assert_eq!(detached.parent_id, None);
+    assert_eq!(detached.name, "Write Test Child");
+
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "PATCH",
+            &format!("/skills/{}", child.id),
+            json!({ "parent_id": parent.id }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Act: Delete the parent while it still has a child
+    let response = router
+        .clone()
+        .oneshot(empty_request("DELETE", &format!("/skills/{}", parent.id)))
+        .await
+        .unwrap();
+
+    // Assert: Deletion is refused without cascade
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // Act: Delete with cascade
+    let response = router
+        .clone()
+        .oneshot(empty_request(
+            "DELETE",
+            &format!("/skills/{}?cascade=true", parent.id),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = router
+        .oneshot(empty_request("GET", &format!("/skills/{}", child.id)))
+        .await
+        .unwrap();
+
+    // Assert: The child went with its parent
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_skill_invalid_payloads() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: An unknown proficiency level
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/skills",
+            json!({
+                "name": "Invalid Skill",
+                "official_site_url": "https://invalid.example",
+                "proficiency": "Guru"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Act: A URL without a scheme
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/skills",
+            json!({
+                "name": "Invalid Skill",
+                "official_site_url": "invalid.example",
+                "proficiency": "Expert"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Act: A parent that does not exist
+    let response = router
+        .oneshot(json_request(
+            "POST",
+            "/skills",
+            json!({
+                "name": "Invalid Skill",
+                "official_site_url": "https://invalid.example",
+                "proficiency": "Expert",
+                "parent_id": 9999
+            }),
+        ))
+        .await
+        .unwrap();
+
+    // Assert: Every payload is rejected with 422
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_delete_skill_used_by_projects() {
+    // Arrange: Set up the router with test DB
+    let router = setup_router_with_test_db().await;
+
+    // Act: Skill 10 is linked to projects
+    let response = router
+        .oneshot(empty_request("DELETE", "/skills/10"))
+        .await
+        .unwrap();
+
+    // Assert: Expect a 409 Conflict response
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_delete_waits_for_concurrent_child() {
+    // Arrange: Create a parent skill
+    let router = setup_router_with_test_db().await;
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/skills",
+            json!({
+                "name": "Concurrent Delete Parent",
+                "official_site_url": "https://parent.example",
+                "proficiency": "Advanced"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let parent = parse_skill(response).await;
+
+    // Arrange: Insert a child in a transaction that is not yet committed
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    let mut tx = pool.begin().await.unwrap();
+    let child_id: i32 = sqlx::query_scalar(
+        "INSERT INTO skills (name, official_site_url, proficiency, parent_id)
+         VALUES ('Concurrent Delete Child', 'https://child.example', 'Beginner', $1)
+         RETURNING id",
+    )
+    .bind(parent.id)
+    .fetch_one(&mut *tx)
+    .await
+    .unwrap();
+
+    // Act: Delete the parent while the child is uncommitted, then commit the child
+    let delete = tokio::spawn(
+        router
+            .clone()
+            .oneshot(empty_request("DELETE", &format!("/skills/{}", parent.id))),
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    tx.commit().await.unwrap();
+    let response = delete.await.unwrap().unwrap();
+
+    // Assert: The delete waited for the child and refused, rather than failing on the
+    // foreign key
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // Cleanup: Remove both skills
+    for id in [child_id, parent.id] {
+        router
+            .clone()
+            .oneshot(empty_request("DELETE", &format!("/skills/{}", id)))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_patch_keeps_concurrent_skill_update() {
+    // Arrange: Create a skill
+    let router = setup_router_with_test_db().await;
+    let response = router
+        .clone()
+        .oneshot(json_request(
+            "POST",
+            "/skills",
+            json!({
+                "name": "Concurrent Patch Skill",
+                "description": "Original",
+                "official_site_url": "https://skill.example",
+                "proficiency": "Beginner"
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let skill = parse_skill(response).await;
+    let uri = format!("/skills/{}", skill.id);
+
+    // Arrange: Change the description in a transaction that is not yet committed
+    let pool = get_test_db_pool().await.expect("Failed to get test DB pool");
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("UPDATE skills SET description = 'Concurrent' WHERE id = $1")
+        .bind(skill.id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    // Act: Patch the name while the description change is uncommitted, then commit it
+    let patch = tokio::spawn(
+        router
+            .clone()
+            .oneshot(json_request("PATCH", &uri, json!({ "name": "Renamed Concurrent Skill" }))),
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    tx.commit().await.unwrap();
+    let response = patch.await.unwrap().unwrap();
+
+    // Assert: The patch waited for the description change instead of overwriting it
+    // with a stale read
+    assert_eq!(response.status(), StatusCode::OK);
+    let patched = parse_skill(response).await;
+    assert_eq!(patched.name, "Renamed Concurrent Skill");
+    assert_eq!(patched.description, "Concurrent");
+
+    // Act & Assert: The merged skill is still validated
+    let response = router.clone().oneshot(json_request("PATCH", &uri, json!({ "name": " " }))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Cleanup: Remove the skill
+    router.oneshot(empty_request("DELETE", &uri)).await.unwrap();
+}